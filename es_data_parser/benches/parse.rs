@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const SAMPLE: &str = include_str!("../../data/simple_game.txt");
+
+// objects near the end of the `alt` chain in `validate`
+const LATE_OBJECTS: &str = r#"color "shields" .43 .55 .70 0.
+
+substitutions
+	"<fruit>" "apple"
+	"<fruit>" "banana"
+		has "tropical"
+
+wormhole "The Eye"
+	mappable
+	link Alpha Beta
+	link Beta Alpha
+	color "shields"
+
+"#;
+
+fn bench_input(c: &mut Criterion, name: &str, data: &str) {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("validate", |b| {
+        b.iter(|| es_data_parser::validate(black_box(data)).unwrap().1.len())
+    });
+    group.bench_function("objects", |b| {
+        b.iter(|| {
+            es_data_parser::objects(black_box(data))
+                .filter(Result::is_ok)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    bench_input(c, "sample", &SAMPLE.repeat(500));
+    bench_input(c, "late objects", &LATE_OBJECTS.repeat(5000));
+}
+
+criterion_group!(benches, parse_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use es_data_parser::{data_files, diff, nodes, Node};
+
+fn read_data(path: &str) -> Result<Vec<(PathBuf, String)>, String> {
+    let error = |path: &Path, error| format!("{}: {}", path.display(), error);
+    data_files(path, error)?
+        .into_iter()
+        .map(|file| {
+            let contents = fs::read_to_string(&file).map_err(|e| error(&file, e))?;
+            Ok((file, contents))
+        })
+        .collect()
+}
+
+fn read_nodes<'a>(files: &'a [(PathBuf, String)], failed: &mut bool) -> Vec<Node<'a>> {
+    let mut all_nodes = vec![];
+    for (file, contents) in files {
+        for node in nodes(contents) {
+            match node {
+                Ok(node) => all_nodes.push(node),
+                Err(diagnostic) => {
+                    eprintln!("{}:{}", file.display(), diagnostic);
+                    *failed = true;
+                }
+            }
+        }
+    }
+    all_nodes
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <old data> <new data>", args[0]);
+        std::process::exit(2);
+    }
+
+    let (old_files, new_files) = match (read_data(&args[1]), read_data(&args[2])) {
+        (Ok(old_files), Ok(new_files)) => (old_files, new_files),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    let old = read_nodes(&old_files, &mut failed);
+    let new = read_nodes(&new_files, &mut failed);
+
+    for difference in diff(&old, &new) {
+        println!("{}", difference);
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// the first byte picks the kind of object, so that each sub-parser gets fuzzed
+fuzz_target!(|data: &[u8]| {
+    if let Some((kind, rest)) = data.split_first() {
+        if let Ok(rest) = std::str::from_utf8(rest) {
+            let kinds = es_data_parser::OBJECT_KINDS;
+            let kind = kinds[*kind as usize % kinds.len()];
+            let data = format!("{} {}", kind, rest);
+            for _ in es_data_parser::objects(&data) {}
+        }
+    }
+});
@@ -0,0 +1,78 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    combinator::opt,
+    error::{context, ParseError},
+    number::complete::float,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::helpers::{eols, string};
+use crate::types::Color;
+
+pub fn parse_color<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Color<'a>, E> {
+    let (input, (_, _, name, _, red, _, green, _, blue, alpha, _)) = context(
+        "color",
+        tuple((
+            tag("color"),
+            space1,
+            string,
+            space1,
+            float,
+            space1,
+            float,
+            space1,
+            float,
+            opt(preceded(space1, float)),
+            eols,
+        )),
+    )(input)?;
+
+    Ok((
+        input,
+        Color {
+            name,
+            red,
+            green,
+            blue,
+            alpha: alpha.unwrap_or(1.0),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_color() {
+        let data = r#"color "shields" .43 .55 .70 0.
+color "bright" 1 1 1
+"#;
+
+        let parsed = dbg!(parse_color::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let (remaining, color) = parsed.unwrap();
+
+        assert_eq!(
+            color,
+            Color {
+                name: "shields",
+                red: 0.43,
+                green: 0.55,
+                blue: 0.70,
+                alpha: 0.0
+            }
+        );
+        assert_eq!(
+            parse_color::<VerboseError<&str>>(remaining)
+                .unwrap()
+                .1
+                .alpha,
+            1.0
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::Node;
+
+/// A difference between two versions of a data set
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference<'a> {
+    /// an object only in the new version
+    Added {
+        /// kind of the object, like `ship`
+        kind: &'a str,
+        /// name of the object
+        name: String,
+    },
+    /// an object only in the old version
+    Removed {
+        /// kind of the object, like `ship`
+        kind: &'a str,
+        /// name of the object
+        name: String,
+    },
+    /// a field of an object that changed
+    Changed {
+        /// kind of the object, like `ship`
+        kind: &'a str,
+        /// name of the object
+        name: String,
+        /// path to the field, like `attributes.cost`, empty for the values after the name
+        path: String,
+        /// value in the old version, if any
+        old: Option<String>,
+        /// value in the new version, if any
+        new: Option<String>,
+    },
+}
+
+impl<'a> fmt::Display for Difference<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difference::Added { kind, name } => write!(f, "+ {} \"{}\"", kind, name),
+            Difference::Removed { kind, name } => write!(f, "- {} \"{}\"", kind, name),
+            Difference::Changed {
+                kind,
+                name,
+                path,
+                old,
+                new,
+            } if path.is_empty() => {
+                write!(f, "{} \"{}\" {} → {}", kind, name, value(old), value(new))
+            }
+            Difference::Changed {
+                kind,
+                name,
+                path,
+                old,
+                new,
+            } => write!(
+                f,
+                "{} \"{}\" {} {} → {}",
+                kind,
+                name,
+                path,
+                value(old),
+                value(new)
+            ),
+        }
+    }
+}
+
+fn value(value: &Option<String>) -> &str {
+    match value.as_deref() {
+        None => "(none)",
+        Some("") => "(present)",
+        Some(value) => value,
+    }
+}
+
+/// Compare two versions of a data set, read as top-level nodes
+///
+/// Objects are matched by kind and name, the last definition winning. Values after the name,
+/// like the components of a `color`, are compared as a field with an empty path. Fields are
+/// compared by their path: a field present once on both sides is reported as changed, while
+/// repeated fields (like `link` or `outfits` entries) are compared as lists of values.
+pub fn diff<'a>(old: &[Node<'a>], new: &[Node<'a>]) -> Vec<Difference<'a>> {
+    let old = Objects::new(old);
+    let new = Objects::new(new);
+    let mut differences = vec![];
+
+    for node in &old.nodes {
+        let (kind, name) = key(node);
+        if new.get(kind, name).is_none() {
+            differences.push(Difference::Removed {
+                kind,
+                name: String::from(name),
+            });
+        }
+    }
+    for node in &new.nodes {
+        let (kind, name) = key(node);
+        match old.get(kind, name) {
+            None => differences.push(Difference::Added {
+                kind,
+                name: String::from(name),
+            }),
+            Some(old_node) => {
+                let mut old_fields = vec![];
+                values(old_node, &mut old_fields);
+                fields(&old_node.children, "", &mut old_fields);
+                let mut new_fields = vec![];
+                values(node, &mut new_fields);
+                fields(&node.children, "", &mut new_fields);
+                for (path, old, new) in diff_fields(old_fields, new_fields) {
+                    differences.push(Difference::Changed {
+                        kind,
+                        name: String::from(name),
+                        path,
+                        old,
+                        new,
+                    });
+                }
+            }
+        }
+    }
+
+    differences
+}
+
+/// Objects by kind and name, keeping the last definition of each
+struct Objects<'n, 'a> {
+    /// objects in the order they were first defined
+    nodes: Vec<&'n Node<'a>>,
+    /// position of each object in `nodes`, by kind and name
+    index: HashMap<(&'a str, &'a str), usize>,
+}
+
+impl<'n, 'a> Objects<'n, 'a> {
+    fn new(nodes: &'n [Node<'a>]) -> Self {
+        let mut objects = Objects {
+            nodes: vec![],
+            index: HashMap::new(),
+        };
+        for node in nodes {
+            match objects.index.get(&key(node)) {
+                Some(&index) => objects.nodes[index] = node,
+                None => {
+                    objects.index.insert(key(node), objects.nodes.len());
+                    objects.nodes.push(node);
+                }
+            }
+        }
+        objects
+    }
+
+    fn get(&self, kind: &str, name: &str) -> Option<&'n Node<'a>> {
+        self.index
+            .get(&(kind, name))
+            .map(|&index| self.nodes[index])
+    }
+}
+
+/// Kind and name of an object
+fn key<'a>(node: &Node<'a>) -> (&'a str, &'a str) {
+    (node.tokens[0], node.tokens.get(1).copied().unwrap_or(""))
+}
+
+/// Values of an object after its name, as a field with an empty path
+fn values(node: &Node, fields: &mut Vec<(String, String)>) {
+    if node.tokens.len() > 2 {
+        fields.push((String::new(), node.tokens[2..].join(" ")));
+    }
+}
+
+/// Flatten nodes to their paths and values
+fn fields(nodes: &[Node], prefix: &str, fields: &mut Vec<(String, String)>) {
+    for node in nodes {
+        if node.tokens.len() == 1 && node.children.is_empty() && !prefix.is_empty() {
+            // an entry of a list, like an outfit name
+            fields.push((String::from(prefix), String::from(node.tokens[0])));
+            continue;
+        }
+        let path = if prefix.is_empty() {
+            String::from(node.tokens[0])
+        } else {
+            format!("{}.{}", prefix, node.tokens[0])
+        };
+        if node.tokens.len() > 1 || node.children.is_empty() {
+            fields.push((path.clone(), node.tokens[1..].join(" ")));
+        }
+        self::fields(&node.children, &path, fields);
+    }
+}
+
+type FieldChange = (String, Option<String>, Option<String>);
+
+fn diff_fields(old: Vec<(String, String)>, new: Vec<(String, String)>) -> Vec<FieldChange> {
+    let mut paths: Vec<&String> = vec![];
+    for (path, _) in old.iter().chain(new.iter()) {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    let mut changes = vec![];
+    for path in paths {
+        let mut old_values = old
+            .iter()
+            .filter(|(p, _)| p == path)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        let mut new_values = new
+            .iter()
+            .filter(|(p, _)| p == path)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        if old_values.len() == 1 && new_values.len() == 1 {
+            if old_values[0] != new_values[0] {
+                changes.push((
+                    path.clone(),
+                    Some(old_values[0].clone()),
+                    Some(new_values[0].clone()),
+                ));
+            }
+            continue;
+        }
+        for value in old_values.clone() {
+            if let Some(index) = new_values.iter().position(|other| *other == value) {
+                new_values.remove(index);
+                let index = old_values.iter().position(|other| *other == value).unwrap();
+                old_values.remove(index);
+            }
+        }
+        changes.extend(
+            old_values
+                .into_iter()
+                .map(|value| (path.clone(), Some(value.clone()), None)),
+        );
+        changes.extend(
+            new_values
+                .into_iter()
+                .map(|value| (path.clone(), None, Some(value.clone()))),
+        );
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff, Difference};
+    use crate::nodes;
+
+    #[test]
+    fn can_diff_objects() {
+        let old = r#"ship "Shuttle"
+	attributes
+		"cost" 180000
+		"mass" 70
+	outfits
+		"Hyperdrive"
+system Sol
+	link Alpha
+wormhole Eye
+	link Alpha Beta
+color "shields" 0.1 0.2 0.3 1.
+"#;
+        let new = r#"ship "Shuttle"
+	attributes
+		"cost" 190000
+		"mass" 70
+	outfits
+		"Hyperdrive"
+		"Afterburner"
+planet Earth
+	description `Home`
+wormhole Eye
+	mappable
+	link Alpha Beta
+color "shields" 0.2 0.2 0.3 1.
+"#;
+        let old = nodes(old).collect::<Result<Vec<_>, _>>().unwrap();
+        let new = nodes(new).collect::<Result<Vec<_>, _>>().unwrap();
+
+        let differences = diff(&old, &new);
+        assert_eq!(
+            differences
+                .iter()
+                .map(|difference| difference.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "- system \"Sol\"",
+                "ship \"Shuttle\" attributes.cost 180000 → 190000",
+                "ship \"Shuttle\" outfits (none) → Afterburner",
+                "+ planet \"Earth\"",
+                "wormhole \"Eye\" mappable (none) → (present)",
+                "color \"shields\" 0.1 0.2 0.3 1. → 0.2 0.2 0.3 1.",
+            ]
+        );
+        assert_eq!(
+            differences[0],
+            Difference::Removed {
+                kind: "system",
+                name: String::from("Sol")
+            }
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use nom::{
+    bytes::complete::tag, character::complete::space1, error::context, number::complete::float,
+    sequence::tuple, IResult,
+};
+
+use crate::helpers::{eols, indent, integer, string};
+use crate::sprite::parse_sprite;
+use crate::types::Effect;
+use crate::DataError;
+
+pub fn parse_effect<'a>(input: &'a str) -> IResult<&'a str, Effect<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) =
+        context("effect tag", tuple((tag("effect"), space1, string, eols)))(input)?;
+
+    let mut builder = crate::types::EffectBuilder::default();
+    builder.name(name);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, sprite, |input| parse_sprite(2, input), input, builder);
+        crate::parse_item_in_loop!(1, sound, "\"sound\"", string, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            lifetime,
+            "\"lifetime\"",
+            integer::<u32, _>,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            random_lifetime,
+            "\"random lifetime\"",
+            integer::<u32, _>,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            velocity_scale,
+            "\"velocity scale\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, random_angle, "\"random angle\"", float, input, builder);
+        crate::parse_item_in_loop!(1, random_spin, "\"random spin\"", float, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            random_velocity,
+            "\"random velocity\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            random_frame_rate,
+            "\"random frame rate\"",
+            float,
+            input,
+            builder
+        );
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|effect| (input, effect))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("effect"),
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{Sprite, SpriteAnimation};
+
+    #[test]
+    fn can_parse_effect() {
+        let data = r#"effect "tiny explosion"
+	sprite "effect/explosion/tiny"
+		"no repeat"
+		"frame rate" 15
+	"sound" "explosion tiny"
+	"lifetime" 16
+	"random angle" 360
+	"random spin" 2
+	"random velocity" 1
+	"velocity scale" -.05
+"#;
+
+        let parsed = dbg!(super::parse_effect(data));
+        assert!(parsed.is_ok());
+        let effect = parsed.unwrap().1;
+
+        assert_eq!(effect.name, "tiny explosion");
+        assert_eq!(
+            effect.sprite,
+            Some(Sprite {
+                name: "effect/explosion/tiny",
+                animation: SpriteAnimation {
+                    frame_rate: 15.,
+                    no_repeat: true,
+                    ..SpriteAnimation::default()
+                }
+            })
+        );
+        assert_eq!(effect.sound, Some("explosion tiny"));
+        assert_eq!(effect.lifetime, Some(16));
+        assert_eq!(effect.random_lifetime, None);
+        assert_eq!(effect.random_angle, Some(360.0));
+        assert_eq!(effect.random_spin, Some(2.0));
+        assert_eq!(effect.random_velocity, Some(1.0));
+        assert_eq!(effect.velocity_scale, Some(-0.05));
+    }
+}
@@ -29,3 +29,74 @@ impl<'a, I> ParseError<I> for DataError<I> {
         }
     }
 }
+
+/// A parsing problem, located in the parsed input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// line of the problem, starting at 1
+    pub line: usize,
+    /// column of the problem, starting at 1
+    pub column: usize,
+    /// offset of the problem in the input, in bytes
+    pub offset: usize,
+    /// what went wrong
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Build a diagnostic at the start of `at`, which must be a slice of `input`
+    pub(crate) fn new(input: &str, at: &str, message: String) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Diagnostic {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+            message,
+        }
+    }
+
+    pub(crate) fn from_error(input: &str, error: nom::Err<DataError<&str>>) -> Self {
+        match error {
+            nom::Err::Incomplete(_) => Diagnostic::new(
+                input,
+                &input[input.len()..],
+                String::from("incomplete input"),
+            ),
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                Diagnostic::from_data_error(input, error)
+            }
+        }
+    }
+
+    fn from_data_error(input: &str, error: DataError<&str>) -> Self {
+        match error {
+            DataError::NomError { input: at, kind } => {
+                Diagnostic::new(input, at, format!("unexpected input ({:?})", kind))
+            }
+            DataError::DataBuilderError {
+                input: at,
+                error,
+                data_type,
+            } => Diagnostic::new(input, at, format!("invalid {}: {}", data_type, error)),
+            DataError::Errors(mut errors) => {
+                if errors.is_empty() {
+                    Diagnostic::new(input, input, String::from("unknown error"))
+                } else {
+                    Diagnostic::from_data_error(input, errors.remove(0))
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
@@ -0,0 +1,73 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Data files at a path: the file itself, or all the `.txt` files below a directory, in path
+/// order
+///
+/// `error` builds the error to return from the path that couldn't be read.
+pub fn data_files<E>(
+    path: impl AsRef<Path>,
+    error: impl Fn(&Path, io::Error) -> E,
+) -> Result<Vec<PathBuf>, E> {
+    let mut files = vec![];
+    let path = path.as_ref();
+    if path.is_dir() {
+        walk(path, &error, &mut files)?;
+    } else {
+        files.push(path.to_path_buf());
+    }
+    Ok(files)
+}
+
+fn walk<E>(
+    dir: &Path,
+    error: &impl Fn(&Path, io::Error) -> E,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), E> {
+    let mut entries = fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|e| error(dir, e))?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            walk(&entry, error, files)?;
+        } else if entry.extension() == Some("txt".as_ref()) {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::data_files;
+
+    #[test]
+    fn can_list_data_files() {
+        let dir = std::env::temp_dir().join(format!("es_data_files_{}", std::process::id()));
+        fs::create_dir_all(dir.join("ships")).unwrap();
+        fs::write(dir.join("map.txt"), "").unwrap();
+        fs::write(dir.join("ships").join("shuttle.txt"), "").unwrap();
+        fs::write(dir.join("notes.md"), "").unwrap();
+
+        let files = data_files(&dir, |_, error| error);
+        let single = data_files(dir.join("notes.md"), |_, error| error);
+        let missing = data_files(dir.join("missing"), |path, _| path.to_path_buf());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            files.unwrap(),
+            vec![dir.join("map.txt"), dir.join("ships").join("shuttle.txt")]
+        );
+        assert_eq!(single.unwrap(), vec![dir.join("notes.md")]);
+        // a missing path is returned as is, to fail when read
+        assert_eq!(missing.unwrap(), vec![dir.join("missing")]);
+    }
+}
@@ -1,7 +1,7 @@
 use nom::{
     branch::permutation,
     bytes::complete::tag,
-    character::complete::{line_ending, space1},
+    character::complete::space1,
     combinator::opt,
     error::{context, ParseError},
     multi::count,
@@ -10,14 +10,12 @@ use nom::{
     IResult,
 };
 
-use crate::helpers::{indent, resource_path, string};
+use crate::helpers::{eol, indent, resource_path, string};
 use crate::types::{Galaxy, Position};
 
 pub fn parse_galaxy<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Galaxy<'a>, E> {
-    let (input, (_, _, name, _)) = context(
-        "galaxy tag",
-        tuple((tag("galaxy"), space1, string, line_ending)),
-    )(input)?;
+    let (input, (_, _, name, _)) =
+        context("galaxy tag", tuple((tag("galaxy"), space1, string, eol)))(input)?;
     let (input, (pos, sprite)) =
         context("galaxy fields", permutation((parse_pos, opt(parse_sprite))))(input)?;
 
@@ -25,15 +23,8 @@ pub fn parse_galaxy<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a s
 }
 
 fn parse_pos<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Position, E> {
-    let (input, (_, _, _, x, _, y, _)) = tuple((
-        indent,
-        tag("pos"),
-        space1,
-        double,
-        space1,
-        double,
-        line_ending,
-    ))(input)?;
+    let (input, (_, _, _, x, _, y, _)) =
+        tuple((indent, tag("pos"), space1, double, space1, double, eol))(input)?;
 
     Ok((input, Position { x, y }))
 }
@@ -0,0 +1,123 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    combinator::opt,
+    error::context,
+    multi::many0,
+    number::complete::float,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+
+use crate::helpers::{eol, eols, indent, integer, node_at_level, string};
+use crate::types::Hazard;
+use crate::DataError;
+
+pub fn parse_hazard<'a>(input: &'a str) -> IResult<&'a str, Hazard<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) =
+        context("hazard tag", tuple((tag("hazard"), space1, string, eols)))(input)?;
+
+    let mut builder = crate::types::HazardBuilder::default();
+    builder.name(name);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(
+            1,
+            weapon,
+            preceded(eol, many0(|input| node_at_level(2, input))),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            constant_strength,
+            "\"constant strength\"",
+            |input| Ok((input, true)),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, period, integer::<u32, _>, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            duration,
+            tuple((integer, opt(preceded(space1, integer)))),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            strength,
+            tuple((float, opt(preceded(space1, float)))),
+            input,
+            builder
+        );
+        crate::parse_items_in_loop!(
+            1,
+            environmental_effect,
+            "\"environmental effect\"",
+            separated_pair(string, space1, integer),
+            input,
+            builder
+        );
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|hazard| (input, hazard))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("hazard"),
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::Node;
+
+    #[test]
+    fn can_parse_hazard() {
+        let data = r#"hazard "Ion Storm"
+	weapon
+		"ion damage" .2
+		"blast radius" 500
+	"constant strength"
+	period 300
+	duration 600 2400
+	strength 2 5
+	"environmental effect" "ion spark" 5
+	"environmental effect" "ion flash" 1
+"#;
+
+        let parsed = dbg!(super::parse_hazard(data));
+        assert!(parsed.is_ok());
+        let hazard = parsed.unwrap().1;
+
+        assert_eq!(hazard.name, "Ion Storm");
+        assert_eq!(
+            hazard.weapon,
+            vec![
+                Node {
+                    tokens: vec!["ion damage", ".2"],
+                    children: vec![]
+                },
+                Node {
+                    tokens: vec!["blast radius", "500"],
+                    children: vec![]
+                },
+            ]
+        );
+        assert!(hazard.constant_strength);
+        assert_eq!(hazard.period, Some(300));
+        assert_eq!(hazard.duration, Some((600, Some(2400))));
+        assert_eq!(hazard.strength, Some((2.0, Some(5.0))));
+        assert_eq!(
+            hazard.environmental_effect,
+            vec![("ion spark", 5), ("ion flash", 1)]
+        );
+    }
+}
@@ -1,15 +1,16 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    bytes::complete::take_until,
-    character::complete::{char, digit1, line_ending, space1, tab},
-    combinator::cut,
+    bytes::complete::{is_not, take_until},
+    character::complete::{char, digit1, line_ending, not_line_ending, space0, space1, tab},
+    combinator::{cut, map_res},
     error::{context, ErrorKind, ParseError},
+    multi::{count, many0, separated_nonempty_list},
     sequence::{preceded, terminated, tuple},
     AsChar, IResult, InputTakeAtPosition,
 };
 
-use crate::types::Date;
+use crate::types::{Date, Node};
 
 fn tab_hole<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
     context("indent to ignore (tab)", tab)(input).map(|(remaining, _)| (remaining, ()))
@@ -39,6 +40,37 @@ pub fn string<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'
     )(input)
 }
 
+/// any token of a line: a quoted string or a run of non whitespace characters
+pub fn token<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    context(
+        "token",
+        alt((
+            preceded(char('"'), cut(terminated(take_until("\""), char('"')))),
+            preceded(char('`'), cut(terminated(take_until("`"), char('`')))),
+            is_not(" \t\r\n"),
+        )),
+    )(input)
+}
+
+/// a line of tokens at the given indentation level, with all the lines indented below it
+pub fn node_at_level<'a, E: ParseError<&'a str>>(
+    level: usize,
+    input: &'a str,
+) -> IResult<&'a str, Node<'a>, E> {
+    let (input, (_, tokens, _, _)) = context(
+        "node",
+        tuple((
+            count(indent, level),
+            separated_nonempty_list(space1, token),
+            space0,
+            eols,
+        )),
+    )(input)?;
+    let (input, children) = many0(|input| node_at_level(level + 1, input))(input)?;
+
+    Ok((input, Node { tokens, children }))
+}
+
 pub fn alphanumeric_or_other_ok1<T, E: ParseError<T>>(input: T) -> IResult<T, T, E>
 where
     T: InputTakeAtPosition,
@@ -58,26 +90,45 @@ pub fn resource_path<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a
         "resource path",
         alt((
             preceded(char('"'), cut(terminated(take_until("\""), char('"')))),
-            take_until("\n"),
+            preceded(char('`'), cut(terminated(take_until("`"), char('`')))),
+            not_line_ending,
         )),
     )(input)
 }
 
 pub fn integer<'a, T: std::str::FromStr, E: ParseError<&'a str>>(
     input: &'a str,
-) -> IResult<&'a str, T, E>
-where
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
-{
-    context("integer", digit1)(input).map(|(input, value)| (input, value.parse::<T>().unwrap()))
+) -> IResult<&'a str, T, E> {
+    context("integer", map_res(digit1, |value: &str| value.parse::<T>()))(input)
+}
+
+/// end of a line, whatever its line ending, or end of the input
+pub fn eol<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    if input.is_empty() {
+        Ok((input, input))
+    } else {
+        line_ending(input)
+    }
+}
+
+/// end of a line followed by any number of empty lines
+pub fn eols<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
+    let (input, _) = eol(input)?;
+    let (input, _) = many0(line_ending)(input)?;
+    Ok((input, ()))
+}
+
+/// remove the byte order mark some editors put at the start of UTF-8 files
+pub fn strip_bom(input: &str) -> &str {
+    input.trim_start_matches('\u{feff}')
 }
 
 pub fn comment_hole<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
     context(
         "comment to ignore",
         alt((
-            tuple((tag("//"), terminated(take_until("\n"), line_ending))),
-            tuple((tag("#"), terminated(take_until("\n"), line_ending))),
+            tuple((tag("//"), terminated(not_line_ending, eol))),
+            tuple((tag("#"), terminated(not_line_ending, eol))),
         )),
     )(input)
     .map(|(remaining, _)| (remaining, ()))
@@ -95,7 +146,7 @@ macro_rules! parse_item_with_indent {
                     tag(stringify!($tag)),
                     space1,
                     $subparser,
-                    line_ending,
+                    $crate::helpers::eol,
                 )),
             )(input)?;
 
@@ -135,7 +186,7 @@ macro_rules! parse_item_in_loop {
                         )),
                         $subparser,
                     ),
-                    nom::multi::many0(line_ending),
+                    nom::multi::many0(nom::character::complete::line_ending),
                 ),
             )($input)?;
             $input = remaining;
@@ -149,26 +200,36 @@ macro_rules! parse_item_in_loop {
 #[macro_export]
 macro_rules! parse_items_in_loop {
     ($nb_indent:expr, $field:ident, $subparser:expr, $input:ident, $builder:ident) => {
+        crate::parse_items_in_loop!(
+            $nb_indent,
+            $field,
+            stringify!($field),
+            $subparser,
+            $input,
+            $builder
+        )
+    };
+    ($nb_indent:expr, $field:ident, $tag:expr, $subparser:expr, $input:ident, $builder:ident) => {
         let peeked: IResult<_, _, (&str, nom::error::ErrorKind)> =
             nom::combinator::peek(nom::sequence::tuple((
                 nom::multi::count(indent, $nb_indent),
-                nom::bytes::complete::tag(stringify!($field)),
+                nom::bytes::complete::tag($tag),
             )))($input);
         if peeked.is_ok() {
             let (remaining, extracted) = nom::error::context(
-                stringify!($field),
+                $tag,
                 nom::multi::many1(nom::sequence::terminated(
                     nom::sequence::preceded(
                         nom::sequence::tuple((
                             nom::multi::count(indent, $nb_indent),
-                            nom::bytes::complete::tag(stringify!($field)),
+                            nom::bytes::complete::tag($tag),
                             nom::combinator::opt(space1),
                         )),
                         $subparser,
                     ),
                     nom::sequence::tuple((
                         nom::multi::many0(indent),
-                        nom::multi::many0(line_ending),
+                        nom::multi::many0(nom::character::complete::line_ending),
                     )),
                 )),
             )($input)?;
@@ -178,3 +239,56 @@ macro_rules! parse_items_in_loop {
         }
     };
 }
+
+/// will parse an item that can be present several times, peeking first if the tag is present, and continuing the loop after pushing it to a list
+#[macro_export]
+macro_rules! push_item_in_loop {
+    ($nb_indent:expr, $tag:expr, $subparser:expr, $input:ident, $list:ident) => {
+        let peeked: IResult<_, _, (&str, nom::error::ErrorKind)> =
+            nom::combinator::peek(nom::sequence::tuple((
+                nom::multi::count(indent, $nb_indent),
+                nom::bytes::complete::tag($tag),
+            )))($input);
+        if peeked.is_ok() {
+            let (remaining, extracted) = nom::error::context(
+                $tag,
+                nom::sequence::terminated(
+                    nom::sequence::preceded(
+                        nom::sequence::tuple((
+                            nom::multi::count(indent, $nb_indent),
+                            nom::bytes::complete::tag($tag),
+                            nom::combinator::opt(space1),
+                        )),
+                        $subparser,
+                    ),
+                    nom::multi::many0(nom::character::complete::line_ending),
+                ),
+            )($input)?;
+            $input = remaining;
+            $list.push(extracted);
+            continue;
+        }
+    };
+}
+
+/// will parse any other line and its children as a node, skipping comments, and continuing the loop after pushing it to a list
+#[macro_export]
+macro_rules! push_other_in_loop {
+    ($nb_indent:expr, $input:ident, $list:ident) => {
+        if let Ok((remaining, _)) = nom::sequence::preceded(
+            nom::multi::count(indent, $nb_indent),
+            crate::helpers::comment_hole::<(&str, nom::error::ErrorKind)>,
+        )($input)
+        {
+            $input = remaining;
+            continue;
+        }
+        if let Ok((remaining, node)) =
+            crate::helpers::node_at_level::<(&str, nom::error::ErrorKind)>($nb_indent, $input)
+        {
+            $input = remaining;
+            $list.push(node);
+            continue;
+        }
+    };
+}
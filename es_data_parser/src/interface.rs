@@ -0,0 +1,170 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    error::{context, ParseError},
+    multi::many0,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::helpers::{eols, node_at_level, string};
+use crate::types::{Interface, InterfaceElement, Node, Position};
+
+pub fn parse_interface<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Interface<'a>, E> {
+    let (input, (_, _, name, _)) = context(
+        "interface tag",
+        tuple((tag("interface"), space1, string, eols)),
+    )(input)?;
+    let (input, nodes) =
+        context("interface elements", many0(|input| node_at_level(1, input)))(input)?;
+
+    let mut interface = Interface {
+        name,
+        anchor: vec![],
+        values: vec![],
+        elements: vec![],
+    };
+    // conditions apply to all the elements that follow them
+    let mut visible_if = None;
+    let mut active_if = None;
+    for node in nodes {
+        match node.tokens.as_slice() {
+            ["anchor", anchor @ ..] => interface.anchor = anchor.to_vec(),
+            ["visible", "if", condition] => visible_if = Some(*condition),
+            ["visible"] => visible_if = None,
+            ["active", "if", condition] => active_if = Some(*condition),
+            ["active"] => active_if = None,
+            ["value", name, value] if value.parse::<f32>().is_ok() => interface
+                .values
+                .push((name, value.parse().unwrap_or_default())),
+            [kind, arguments @ ..] => {
+                let mut element = InterfaceElement {
+                    kind,
+                    arguments: arguments.to_vec(),
+                    visible_if,
+                    active_if,
+                    center: None,
+                    dimensions: None,
+                    from: None,
+                    to: None,
+                    align: vec![],
+                    size: None,
+                    color: None,
+                    others: vec![],
+                };
+                node.children
+                    .into_iter()
+                    .for_each(|property| add_property(&mut element, property));
+                interface.elements.push(element);
+            }
+            [] => (),
+        }
+    }
+
+    Ok((input, interface))
+}
+
+fn add_property<'a>(element: &mut InterfaceElement<'a>, property: Node<'a>) {
+    match property.tokens.as_slice() {
+        ["center", x, y] if position(x, y).is_some() => element.center = position(x, y),
+        ["dimensions", x, y] if position(x, y).is_some() => element.dimensions = position(x, y),
+        ["from", x, y] if position(x, y).is_some() => element.from = position(x, y),
+        ["from", x0, y0, "to", x1, y1] if position(x0, y0).and(position(x1, y1)).is_some() => {
+            element.from = position(x0, y0);
+            element.to = position(x1, y1);
+        }
+        ["to", x, y] if position(x, y).is_some() => element.to = position(x, y),
+        ["align", align @ ..] => element.align = align.to_vec(),
+        ["size", size] if size.parse::<f32>().is_ok() => element.size = size.parse().ok(),
+        ["color", color] => element.color = Some(color),
+        ["visible", "if", condition] => element.visible_if = Some(condition),
+        ["active", "if", condition] => element.active_if = Some(condition),
+        _ => element.others.push(property),
+    }
+}
+
+fn position(x: &str, y: &str) -> Option<Position> {
+    Some(Position {
+        x: x.parse().ok()?,
+        y: y.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_interface() {
+        let data = r#"interface "hud"
+	anchor top right
+	value "radar radius" 110
+	sprite "ui/radar"
+		center -110 110
+	visible if "has flagship"
+	label "Fuel"
+		from -200 10 to -100 30
+		align left
+		color "medium"
+		size 14
+	bar "fuel"
+		from -100 10
+		to -10 30
+		"reversed"
+	visible
+	button l "_Land"
+		center -60 -60
+		dimensions 100 30
+"#;
+
+        let parsed = dbg!(parse_interface::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let interface = parsed.unwrap().1;
+
+        assert_eq!(interface.name, "hud");
+        assert_eq!(interface.anchor, vec!["top", "right"]);
+        assert_eq!(interface.values, vec![("radar radius", 110.0)]);
+        assert_eq!(interface.elements.len(), 4);
+
+        let sprite = &interface.elements[0];
+        assert_eq!(sprite.kind, "sprite");
+        assert_eq!(sprite.arguments, vec!["ui/radar"]);
+        assert_eq!(
+            sprite.center,
+            Some(Position {
+                x: -110.0,
+                y: 110.0
+            })
+        );
+        assert_eq!(sprite.visible_if, None);
+
+        let label = &interface.elements[1];
+        assert_eq!(label.kind, "label");
+        assert_eq!(label.visible_if, Some("has flagship"));
+        assert_eq!(label.from, Some(Position { x: -200.0, y: 10.0 }));
+        assert_eq!(label.to, Some(Position { x: -100.0, y: 30.0 }));
+        assert_eq!(label.align, vec!["left"]);
+        assert_eq!(label.color, Some("medium"));
+        assert_eq!(label.size, Some(14.0));
+
+        let bar = &interface.elements[2];
+        assert_eq!(bar.from, Some(Position { x: -100.0, y: 10.0 }));
+        assert_eq!(bar.to, Some(Position { x: -10.0, y: 30.0 }));
+        assert_eq!(
+            bar.others,
+            vec![Node {
+                tokens: vec!["reversed"],
+                children: vec![]
+            }]
+        );
+
+        let button = &interface.elements[3];
+        assert_eq!(button.arguments, vec!["l", "_Land"]);
+        assert_eq!(button.visible_if, None);
+        assert_eq!(button.dimensions, Some(Position { x: 100.0, y: 30.0 }));
+    }
+}
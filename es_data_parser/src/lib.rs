@@ -19,15 +19,37 @@ use nom::{
 mod types;
 pub use types::*;
 
+mod color;
+mod diff;
+pub use diff::{diff, Difference};
+mod effect;
 mod errors;
+mod files;
+pub use files::data_files;
 mod helpers;
 use errors::DataError;
+pub use errors::Diagnostic;
 
 mod galaxy;
+mod hazard;
+mod interface;
+mod news;
+mod objects;
+pub use objects::{blocks, known_objects, nodes, objects, OBJECT_KINDS};
+mod phrase;
 mod planet;
+mod sale;
+mod save;
 mod ship;
+mod sprite;
 mod start;
+mod substitutions;
 mod system;
+mod trade;
+mod wormhole;
+mod writer;
+pub use wormhole::wormhole_links;
+pub use writer::Token;
 
 /// Parse Endless Sky data, returning a list of objects parsed or an empty list on error
 pub fn parse<'a>(input: &'a str) -> Vec<Object<'a>> {
@@ -38,6 +60,7 @@ pub fn parse<'a>(input: &'a str) -> Vec<Object<'a>> {
 
 /// Parse Endless Sky data
 pub fn validate<'a>(input: &'a str) -> IResult<&'a str, Vec<Object<'a>>, DataError<&'a str>> {
+    let input = helpers::strip_bom(input);
     all_consuming(many1(alt((
         |input| {
             start::parse_start(input).map(|(input, parsed)| (input, Some(Object::Start(parsed))))
@@ -52,6 +75,42 @@ pub fn validate<'a>(input: &'a str) -> IResult<&'a str, Vec<Object<'a>>, DataErr
             system::parse_system(input).map(|(input, parsed)| (input, Some(Object::System(parsed))))
         },
         |input| ship::parse_ship(input).map(|(input, parsed)| (input, Some(Object::Ship(parsed)))),
+        |input| {
+            sale::parse_shipyard(input)
+                .map(|(input, parsed)| (input, Some(Object::Shipyard(parsed))))
+        },
+        |input| {
+            sale::parse_outfitter(input)
+                .map(|(input, parsed)| (input, Some(Object::Outfitter(parsed))))
+        },
+        |input| {
+            trade::parse_trade(input).map(|(input, parsed)| (input, Some(Object::Trade(parsed))))
+        },
+        |input| {
+            phrase::parse_phrase(input).map(|(input, parsed)| (input, Some(Object::Phrase(parsed))))
+        },
+        |input| news::parse_news(input).map(|(input, parsed)| (input, Some(Object::News(parsed)))),
+        |input| {
+            effect::parse_effect(input).map(|(input, parsed)| (input, Some(Object::Effect(parsed))))
+        },
+        |input| {
+            hazard::parse_hazard(input).map(|(input, parsed)| (input, Some(Object::Hazard(parsed))))
+        },
+        |input| {
+            color::parse_color(input).map(|(input, parsed)| (input, Some(Object::Color(parsed))))
+        },
+        |input| {
+            interface::parse_interface(input)
+                .map(|(input, parsed)| (input, Some(Object::Interface(parsed))))
+        },
+        |input| {
+            substitutions::parse_substitutions(input)
+                .map(|(input, parsed)| (input, Some(Object::Substitutions(parsed))))
+        },
+        |input| {
+            wormhole::parse_wormhole(input)
+                .map(|(input, parsed)| (input, Some(Object::Wormhole(parsed))))
+        },
         |input| line_ending(input).map(|(input, _)| (input, None)),
         |input| helpers::comment_hole(input).map(|(input, _)| (input, None)),
     ))))(input)
@@ -63,9 +122,27 @@ pub fn validate<'a>(input: &'a str) -> IResult<&'a str, Vec<Object<'a>>, DataErr
     })
 }
 
+/// Parse a save file of a player
+pub fn validate_save_game<'a>(
+    input: &'a str,
+) -> IResult<&'a str, SaveGame<'a>, DataError<&'a str>> {
+    all_consuming(save::parse_save_game)(helpers::strip_bom(input))
+}
+
+/// Read a save file of a player, locating the problem if it can't be read
+pub fn read_save_game(input: &str) -> Result<SaveGame<'_>, Diagnostic> {
+    validate_save_game(input)
+        .map(|(_, save_game)| save_game)
+        .map_err(|error| Diagnostic::from_error(input, error))
+}
+
 #[cfg(test)]
 mod test {
-    use super::validate;
+    use proptest::prelude::*;
+
+    use super::{nodes, objects, validate, validate_save_game};
+
+    const SAMPLE: &str = include_str!("../../data/simple_game.txt");
 
     #[test]
     fn will_fail_for_empty_input() {
@@ -109,4 +186,45 @@ start
         let parsed = dbg!(validate(data));
         assert!(parsed.is_ok());
     }
+
+    #[test]
+    fn can_parse_crlf_bom_and_no_final_newline() {
+        let windows = format!("\u{feff}{}", SAMPLE.replace('\n', "\r\n").trim_end());
+
+        let parsed = validate(&windows);
+        assert!(parsed.is_ok());
+        assert_eq!(
+            format!("{:?}", parsed.unwrap().1),
+            format!("{:?}", validate(SAMPLE).unwrap().1)
+        );
+        assert_eq!(
+            format!("{:?}", objects(&windows).collect::<Vec<_>>()),
+            format!("{:?}", objects(SAMPLE).collect::<Vec<_>>())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn will_not_panic_on_any_input(data in "[a-z0-9 \t\n\r\"`#./-]{0,200}") {
+            let _ = validate(&data);
+            let _ = validate_save_game(&data);
+            let _ = objects(&data).count();
+            let _ = nodes(&data).count();
+        }
+
+        #[test]
+        fn will_not_panic_on_altered_data(
+            start in 0..SAMPLE.len(),
+            length in 0..40usize,
+            insert in "[a-z0-9 \t\n\"`-]{0,20}",
+        ) {
+            let start = (0..=start).rev().find(|i| SAMPLE.is_char_boundary(*i)).unwrap();
+            let end = (start + length).min(SAMPLE.len());
+            let end = (end..=SAMPLE.len()).find(|i| SAMPLE.is_char_boundary(*i)).unwrap();
+            let data = format!("{}{}{}", &SAMPLE[..start], insert, &SAMPLE[end..]);
+            let _ = validate(&data);
+            let _ = objects(&data).count();
+            let _ = nodes(&data).count();
+        }
+    }
 }
@@ -0,0 +1,122 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    error::context,
+    multi::{count, many1},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::helpers::{eol, eols, indent, node_at_level, string};
+use crate::phrase::parse_phrase_parts_at_level;
+use crate::types::News;
+use crate::DataError;
+
+pub fn parse_news<'a>(input: &'a str) -> IResult<&'a str, News<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) =
+        context("news tag", tuple((tag("news"), space1, string, eols)))(input)?;
+
+    let mut builder = crate::types::NewsBuilder::default();
+    builder.name(name);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(
+            1,
+            location,
+            preceded(eol, many1(|input| node_at_level(2, input))),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            speaker,
+            "name",
+            preceded(eol, |input| parse_phrase_parts_at_level(2, input)),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            portrait,
+            preceded(
+                eol,
+                many1(preceded(count(indent, 2), terminated(string, eol)))
+            ),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            message,
+            preceded(eol, |input| parse_phrase_parts_at_level(2, input)),
+            input,
+            builder
+        );
+
+        break;
+    }
+
+    builder.build().map(|news| (input, news)).map_err(|error| {
+        nom::Err::Failure(DataError::DataBuilderError {
+            input,
+            error,
+            data_type: String::from("news"),
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{Node, PhrasePart};
+
+    #[test]
+    fn can_parse_news() {
+        let data = r#"news "farmers"
+	location
+		attributes "dirt belt" farming
+		not
+			planet "New Boston"
+	name
+		word
+			"Farmer"
+	portrait
+		"scene/farmer"
+		"scene/farmer2"
+	message
+		word
+			"Rain again!"
+			"No rain again!" 2
+"#;
+
+        let parsed = dbg!(super::parse_news(data));
+        assert!(parsed.is_ok());
+        let news = parsed.unwrap().1;
+
+        assert_eq!(news.name, "farmers");
+        assert_eq!(
+            news.location,
+            vec![
+                Node {
+                    tokens: vec!["attributes", "dirt belt", "farming"],
+                    children: vec![],
+                },
+                Node {
+                    tokens: vec!["not"],
+                    children: vec![Node {
+                        tokens: vec!["planet", "New Boston"],
+                        children: vec![],
+                    }],
+                },
+            ]
+        );
+        assert_eq!(news.speaker, vec![PhrasePart::Word(vec![("Farmer", 1)])]);
+        assert_eq!(news.portrait, vec!["scene/farmer", "scene/farmer2"]);
+        assert_eq!(
+            news.message,
+            vec![PhrasePart::Word(vec![
+                ("Rain again!", 1),
+                ("No rain again!", 2)
+            ])]
+        );
+    }
+}
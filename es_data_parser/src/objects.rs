@@ -0,0 +1,246 @@
+use nom::{combinator::all_consuming, IResult};
+
+use crate::errors::{DataError, Diagnostic};
+use crate::helpers::node_at_level;
+use crate::{
+    color, effect, galaxy, hazard, interface, news, phrase, planet, sale, ship, start,
+    substitutions, system, trade, wormhole, Node, Object,
+};
+
+/// Parse Endless Sky data lazily, one top-level object at a time
+///
+/// Each object starts on a line without indentation, and the parser to use is chosen from the
+/// first token of that line. A block that fails to parse yields a `Diagnostic`, and parsing
+/// resumes with the next block.
+pub fn objects<'a>(input: &'a str) -> impl Iterator<Item = Result<Object<'a>, Diagnostic>> + 'a {
+    blocks(input).map(move |block| block.and_then(|block| parse_block(input, block)))
+}
+
+/// Parse the objects of the kinds listed in `OBJECT_KINDS` lazily, like `objects`, skipping the
+/// blocks of other kinds, like missions or outfits
+pub fn known_objects<'a>(
+    input: &'a str,
+) -> impl Iterator<Item = Result<Object<'a>, Diagnostic>> + 'a {
+    blocks(input)
+        .filter(|block| {
+            block
+                .as_ref()
+                .map_or(true, |block| OBJECT_KINDS.contains(&block_kind(block)))
+        })
+        .map(move |block| block.and_then(|block| parse_block(input, block)))
+}
+
+/// Kinds of top-level objects that `objects` can parse
+pub const OBJECT_KINDS: &[&str] = &[
+    "start",
+    "planet",
+    "galaxy",
+    "system",
+    "ship",
+    "shipyard",
+    "outfitter",
+    "trade",
+    "phrase",
+    "news",
+    "effect",
+    "hazard",
+    "color",
+    "interface",
+    "substitutions",
+    "wormhole",
+];
+
+/// Split Endless Sky data lazily in top-level blocks, each starting on a line without
+/// indentation and ending before the next one
+pub fn blocks<'a>(input: &'a str) -> impl Iterator<Item = Result<&'a str, Diagnostic>> + 'a {
+    Blocks {
+        input,
+        remaining: crate::helpers::strip_bom(input),
+    }
+}
+
+/// Read Endless Sky data lazily as trees of nodes, one top-level node at a time, without
+/// interpreting them
+pub fn nodes<'a>(input: &'a str) -> impl Iterator<Item = Result<Node<'a>, Diagnostic>> + 'a {
+    blocks(input).map(move |block| {
+        block.and_then(|block| {
+            all_consuming(|block| node_at_level::<DataError<&str>>(0, block))(block)
+                .map(|(_, node)| node)
+                .map_err(|error| Diagnostic::from_error(input, error))
+        })
+    })
+}
+
+/// Top-level blocks of the input, each without its trailing blank lines
+struct Blocks<'a> {
+    input: &'a str,
+    remaining: &'a str,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Result<&'a str, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining.is_empty() {
+                return None;
+            }
+            let (line, after) = split_line(self.remaining);
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || is_comment(line) {
+                self.remaining = after;
+                continue;
+            }
+            let block = next_block(self.remaining);
+            self.remaining = &self.remaining[block.len()..];
+            if trimmed.len() != line.len() {
+                return Some(Err(Diagnostic::new(
+                    self.input,
+                    block,
+                    String::from("indented line outside of an object"),
+                )));
+            }
+            return Some(Ok(block));
+        }
+    }
+}
+
+fn parse_block<'a>(input: &'a str, block: &'a str) -> Result<Object<'a>, Diagnostic> {
+    let kind = block_kind(block);
+    match parse_object(kind, block) {
+        Some(Ok((remaining, object))) => {
+            if remaining.trim().is_empty() {
+                Ok(object)
+            } else {
+                Err(Diagnostic::new(
+                    input,
+                    remaining,
+                    format!("unexpected content in {}", kind),
+                ))
+            }
+        }
+        Some(Err(error)) => Err(Diagnostic::from_error(input, error)),
+        None => Err(Diagnostic::new(
+            input,
+            block,
+            format!("unknown object type \"{}\"", kind),
+        )),
+    }
+}
+
+/// First token of a block, giving the kind of object it defines
+fn block_kind(block: &str) -> &str {
+    block
+        .split(|c: char| c.is_whitespace())
+        .next()
+        .unwrap_or("")
+}
+
+fn parse_object<'a>(
+    kind: &str,
+    block: &'a str,
+) -> Option<IResult<&'a str, Object<'a>, DataError<&'a str>>> {
+    Some(match kind {
+        "start" => start::parse_start(block).map(|(i, o)| (i, Object::Start(o))),
+        "planet" => planet::parse_planet(block).map(|(i, o)| (i, Object::Planet(o))),
+        "galaxy" => galaxy::parse_galaxy(block).map(|(i, o)| (i, Object::Galaxy(o))),
+        "system" => system::parse_system(block).map(|(i, o)| (i, Object::System(o))),
+        "ship" => ship::parse_ship(block).map(|(i, o)| (i, Object::Ship(o))),
+        "shipyard" => sale::parse_shipyard(block).map(|(i, o)| (i, Object::Shipyard(o))),
+        "outfitter" => sale::parse_outfitter(block).map(|(i, o)| (i, Object::Outfitter(o))),
+        "trade" => trade::parse_trade(block).map(|(i, o)| (i, Object::Trade(o))),
+        "phrase" => phrase::parse_phrase(block).map(|(i, o)| (i, Object::Phrase(o))),
+        "news" => news::parse_news(block).map(|(i, o)| (i, Object::News(o))),
+        "effect" => effect::parse_effect(block).map(|(i, o)| (i, Object::Effect(o))),
+        "hazard" => hazard::parse_hazard(block).map(|(i, o)| (i, Object::Hazard(o))),
+        "color" => color::parse_color(block).map(|(i, o)| (i, Object::Color(o))),
+        "interface" => interface::parse_interface(block).map(|(i, o)| (i, Object::Interface(o))),
+        "substitutions" => {
+            substitutions::parse_substitutions(block).map(|(i, o)| (i, Object::Substitutions(o)))
+        }
+        "wormhole" => wormhole::parse_wormhole(block).map(|(i, o)| (i, Object::Wormhole(o))),
+        _ => return None,
+    })
+}
+
+fn split_line(input: &str) -> (&str, &str) {
+    match input.find('\n') {
+        Some(end) => (&input[..end], &input[end + 1..]),
+        None => (input, ""),
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// Find the block starting at `input`: its first line, and all following lines that are indented
+/// or blank, without the trailing blank lines
+fn next_block(input: &str) -> &str {
+    let (_, mut rest) = split_line(input);
+    let mut end = input.len() - rest.len();
+    while !rest.is_empty() {
+        let (line, after) = split_line(rest);
+        if line.trim().is_empty() {
+            rest = after;
+            continue;
+        }
+        if !line.starts_with(|c: char| c.is_whitespace()) {
+            break;
+        }
+        rest = after;
+        end = input.len() - rest.len();
+    }
+    &input[..end]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{known_objects, objects};
+    use crate::Object;
+
+    #[test]
+    fn can_stream_objects() {
+        let data = r#"
+# a comment
+color "my color" 0.5 0.5 0.5 1.
+
+planet MyPlanet
+	landscape my/picture
+	description `A planet`
+
+galaxy "Milky Way"
+	pos 10 20
+	sprite ui/galaxy
+"#;
+        let parsed = objects(data).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(parsed.len(), 3);
+        match &parsed[1] {
+            Object::Planet(planet) => assert_eq!(planet.landscape, Some("my/picture")),
+            other => panic!("expected a planet, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn will_report_errors_and_continue() {
+        let data =
+            "color red 1 0 0 1\nmystery thing\n\tpos 1 2\nplanet Earth\n\tdescription `Home`\n";
+        let parsed = objects(data).collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 3);
+        assert!(parsed[0].is_ok());
+        let error = parsed[1].as_ref().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(parsed[2].is_ok());
+
+        // unknown kinds are skipped, but errors in known ones are still reported
+        let data = format!("{}planet Broken\n", data);
+        let parsed = known_objects(&data).collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 3);
+        assert!(parsed[0].is_ok() && parsed[1].is_ok());
+        assert!(parsed[2]
+            .as_ref()
+            .unwrap_err()
+            .message
+            .contains("description"));
+    }
+}
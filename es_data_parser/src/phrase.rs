@@ -0,0 +1,204 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::space1,
+    combinator::opt,
+    error::{context, ParseError},
+    multi::{count, many0, many1},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use rand::Rng;
+
+use crate::helpers::{eols, indent, integer, string};
+use crate::types::{Phrase, PhrasePart};
+
+/// how deep phrases can reference other phrases, to stop on cycles
+const MAX_PHRASE_DEPTH: usize = 32;
+
+pub fn parse_phrase<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Phrase<'a>, E> {
+    let (input, (_, _, name, _)) =
+        context("phrase tag", tuple((tag("phrase"), space1, string, eols)))(input)?;
+    let (input, parts) = context("phrase parts", |input| {
+        parse_phrase_parts_at_level(1, input)
+    })(input)?;
+
+    Ok((input, Phrase { name, parts }))
+}
+
+/// parse `word` and `phrase` blocks at the given indentation level
+pub fn parse_phrase_parts_at_level<'a, E: ParseError<&'a str>>(
+    level: usize,
+    input: &'a str,
+) -> IResult<&'a str, Vec<PhrasePart<'a>>, E> {
+    many0(alt((
+        |input| {
+            parse_choices_at_level(level, "word", input)
+                .map(|(input, choices)| (input, PhrasePart::Word(choices)))
+        },
+        |input| {
+            parse_choices_at_level(level, "phrase", input)
+                .map(|(input, choices)| (input, PhrasePart::Phrase(choices)))
+        },
+    )))(input)
+}
+
+fn parse_choices_at_level<'a, E: ParseError<&'a str>>(
+    level: usize,
+    kind: &'static str,
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, u32)>, E> {
+    let (input, _) = context(kind, tuple((count(indent, level), tag(kind), eols)))(input)?;
+    many1(terminated(
+        preceded(
+            count(indent, level + 1),
+            tuple((string, opt(preceded(space1, integer)))),
+        ),
+        eols,
+    ))(input)
+    .map(|(input, choices)| {
+        (
+            input,
+            choices
+                .into_iter()
+                .map(|(choice, weight)| (choice, weight.unwrap_or(1)))
+                .collect(),
+        )
+    })
+}
+
+impl<'a> Phrase<'a> {
+    /// Generate a text from this phrase. Phrases referenced are looked up in `phrases`, choosing
+    /// randomly between definitions with the same name. The same seeded `rng` will always give
+    /// the same text.
+    pub fn generate<R: Rng + ?Sized>(&self, phrases: &[Phrase<'a>], rng: &mut R) -> String {
+        let mut text = String::new();
+        generate_parts(&self.parts, phrases, rng, 0, &mut text);
+        text
+    }
+}
+
+/// Generate a text from a list of phrase parts, looking up phrases referenced in `phrases`
+pub fn generate_parts<'a, R: Rng + ?Sized>(
+    parts: &[PhrasePart<'a>],
+    phrases: &[Phrase<'a>],
+    rng: &mut R,
+    depth: usize,
+    text: &mut String,
+) {
+    if depth > MAX_PHRASE_DEPTH {
+        return;
+    }
+    for part in parts {
+        match part {
+            PhrasePart::Word(choices) => text.push_str(choose(choices, rng)),
+            PhrasePart::Phrase(choices) => {
+                let name = choose(choices, rng);
+                let definitions = phrases
+                    .iter()
+                    .filter(|phrase| phrase.name == name)
+                    .collect::<Vec<_>>();
+                if !definitions.is_empty() {
+                    let phrase = definitions[rng.gen_range(0, definitions.len())];
+                    generate_parts(&phrase.parts, phrases, rng, depth + 1, text);
+                }
+            }
+        }
+    }
+}
+
+fn choose<'a, R: Rng + ?Sized>(choices: &[(&'a str, u32)], rng: &mut R) -> &'a str {
+    // summed in 64 bits, as large weights could overflow
+    let total: u64 = choices.iter().map(|(_, weight)| u64::from(*weight)).sum();
+    if total == 0 {
+        return "";
+    }
+    let mut picked = rng.gen_range(0, total);
+    for (choice, weight) in choices {
+        let weight = u64::from(*weight);
+        if picked < weight {
+            return choice;
+        }
+        picked -= weight;
+    }
+    ""
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+    use rand::SeedableRng;
+
+    #[test]
+    fn can_parse_phrase() {
+        let data = r#"phrase "pirate"
+	word
+		"Black"
+		Red 3
+	word
+		" "
+	phrase
+		"pirate ships"
+"#;
+
+        let parsed = dbg!(parse_phrase::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let phrase = parsed.unwrap().1;
+
+        assert_eq!(phrase.name, "pirate");
+        assert_eq!(
+            phrase.parts,
+            vec![
+                PhrasePart::Word(vec![("Black", 1), ("Red", 3)]),
+                PhrasePart::Word(vec![(" ", 1)]),
+                PhrasePart::Phrase(vec![("pirate ships", 1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_generate_phrase() {
+        let data = r#"phrase "greeting"
+	word
+		"Hello"
+	word
+		", "
+	phrase
+		"name"
+
+phrase "name"
+	word
+		"Captain"
+		"Pilot"
+	phrase
+		"greeting"
+"#;
+
+        let phrases = crate::parse(data)
+            .into_iter()
+            .filter_map(|object| match object {
+                crate::Object::Phrase(phrase) => Some(phrase),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let generated = phrases[0].generate(&phrases, &mut rand::rngs::StdRng::seed_from_u64(7));
+
+        assert!(generated.starts_with("Hello, Captain") || generated.starts_with("Hello, Pilot"));
+        assert_eq!(
+            generated,
+            phrases[0].generate(&phrases, &mut rand::rngs::StdRng::seed_from_u64(7))
+        );
+    }
+
+    #[test]
+    fn can_choose_with_large_weights() {
+        let choices = [("Black", u32::MAX), ("Red", u32::MAX), ("Blue", 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        for _ in 0..100 {
+            assert_ne!(choose(&choices, &mut rng), "Blue");
+        }
+    }
+}
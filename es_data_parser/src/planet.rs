@@ -1,7 +1,7 @@
 use nom::{
     branch::permutation,
     bytes::complete::tag,
-    character::complete::{line_ending, space1},
+    character::complete::space1,
     error::{context, ParseError},
     multi::{count, separated_list},
     number::complete::float,
@@ -9,15 +9,13 @@ use nom::{
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
+use crate::helpers::{eol, indent, integer, resource_path, string};
 use crate::types::{Fleet, Planet, Tribute};
 use crate::DataError;
 
 pub fn parse_planet<'a>(input: &'a str) -> IResult<&'a str, Planet<'a>, DataError<&'a str>> {
-    let (input, (_, _, name, _)) = context(
-        "planet tag",
-        tuple((tag("planet"), space1, string, line_ending)),
-    )(input)?;
+    let (input, (_, _, name, _)) =
+        context("planet tag", tuple((tag("planet"), space1, string, eol)))(input)?;
 
     let mut builder = crate::types::PlanetBuilder::default();
     builder.name(name);
@@ -48,6 +46,7 @@ pub fn parse_planet<'a>(input: &'a str) -> IResult<&'a str, Planet<'a>, DataErro
             builder
         );
         crate::parse_item_in_loop!(1, tribute, parse_tribute, input, builder);
+        crate::parse_item_in_loop!(1, wormhole, |input| Ok((input, true)), input, builder);
 
         break;
     }
@@ -65,7 +64,7 @@ pub fn parse_planet<'a>(input: &'a str) -> IResult<&'a str, Planet<'a>, DataErro
 }
 
 fn parse_tribute<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Tribute<'a>, E> {
-    let (input, (value, _)) = tuple((integer, line_ending))(input)?;
+    let (input, (value, _)) = tuple((integer, eol))(input)?;
 
     let (input, (threshold, fleet)) = permutation((parse_threshold, parse_fleet))(input)?;
 
@@ -88,7 +87,7 @@ fn parse_fleet<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, F
         string,
         space1,
         integer,
-        line_ending,
+        eol,
     ))(input)?;
 
     Ok((input, Fleet { kind, count }))
@@ -117,6 +116,7 @@ mod test {
 	tribute 1000
 		threshold 3000
 		fleet "Impressive Fleet" 18
+	wormhole
 "#;
 
         let parsed = dbg!(parse_planet(&data));
@@ -161,6 +161,7 @@ mod test {
                     count: 18,
                 }
             })
-        )
+        );
+        assert!(planet.wormhole);
     }
 }
@@ -0,0 +1,165 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    error::{context, ParseError},
+    multi::many0,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::helpers::{eol, eols, indent, string};
+use crate::types::{Object, Outfitter, Planet, Sales, Ship, Shipyard};
+
+pub fn parse_shipyard<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Shipyard<'a>, E> {
+    let (input, (_, _, name, _)) = context(
+        "shipyard tag",
+        tuple((tag("shipyard"), space1, string, eol)),
+    )(input)?;
+    let (input, ships) = context("shipyard items", many0(parse_item))(input)?;
+
+    Ok((input, Shipyard { name, ships }))
+}
+
+pub fn parse_outfitter<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Outfitter<'a>, E> {
+    let (input, (_, _, name, _)) = context(
+        "outfitter tag",
+        tuple((tag("outfitter"), space1, string, eol)),
+    )(input)?;
+    let (input, outfits) = context("outfitter items", many0(parse_item))(input)?;
+
+    Ok((input, Outfitter { name, outfits }))
+}
+
+fn parse_item<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    terminated(preceded(indent, string), eols)(input)
+}
+
+impl<'a> Planet<'a> {
+    /// Resolve the ships and outfits sold on this planet from the lists found in `objects`.
+    /// Lists with the same name are merged, and items are only listed once.
+    pub fn sales(&self, objects: &'a [Object<'a>]) -> Sales<'a> {
+        let mut ships = vec![];
+        let mut outfits = vec![];
+        for object in objects {
+            match object {
+                Object::Shipyard(shipyard) if self.shipyard.contains(&shipyard.name) => {
+                    for ship_name in &shipyard.ships {
+                        let ship = objects.iter().find_map(|object| match object {
+                            Object::Ship(ship) if ship.name == *ship_name => Some(ship),
+                            _ => None,
+                        });
+                        if let Some(ship) = ship {
+                            if !ships.iter().any(|known: &&Ship| known.name == ship.name) {
+                                ships.push(ship);
+                            }
+                        }
+                    }
+                }
+                Object::Outfitter(outfitter) if self.outfitter.contains(&outfitter.name) => {
+                    for outfit in &outfitter.outfits {
+                        if !outfits.contains(outfit) {
+                            outfits.push(*outfit);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Sales { ships, outfits }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_shipyard() {
+        let data = r#"shipyard "Basic Ships"
+	"Shuttle"
+	Flivver
+
+	"Star Barge"
+"#;
+
+        let parsed = dbg!(parse_shipyard::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let shipyard = parsed.unwrap().1;
+
+        assert_eq!(shipyard.name, "Basic Ships");
+        assert_eq!(shipyard.ships, vec!["Shuttle", "Flivver", "Star Barge"]);
+    }
+
+    #[test]
+    fn can_resolve_sales_of_a_planet() {
+        let data = r#"planet "New Boston"
+	description `A planet.`
+	shipyard "Basic Ships"
+	outfitter "Basic Outfits"
+	outfitter "Ammo South"
+
+shipyard "Basic Ships"
+	"Shuttle"
+	"Unknown Ship"
+
+outfitter "Basic Outfits"
+	"Hyperdrive"
+	"Meteor Missile"
+
+outfitter "Ammo South"
+	"Meteor Missile"
+	"Meteor Missile Launcher"
+
+outfitter "Elsewhere"
+	"Blaster"
+
+ship "Shuttle"
+	sprite "ship/shuttle"
+	thumbnail "thumbnail/shuttle"
+	attributes
+		category "Transport"
+		"cost" 180000
+		"hull" 600
+		"mass" 70
+		"drag" 1.7
+		"heat dissipation" .8
+		"outfit space" 120
+		"engine capacity" 60
+		weapon
+			"blast radius" 12
+			"shield damage" 120
+			"hull damage" 60
+			"hit force" 180
+	outfits
+		"Hyperdrive"
+	engine -6 30
+	explode "tiny explosion" 10
+	description "A shuttle."
+"#;
+
+        let objects = crate::parse(data);
+        let planet = objects
+            .iter()
+            .find_map(|object| match object {
+                Object::Planet(planet) => Some(planet),
+                _ => None,
+            })
+            .unwrap();
+        let sales = planet.sales(&objects);
+
+        assert_eq!(
+            sales.ships.iter().map(|ship| ship.name).collect::<Vec<_>>(),
+            vec!["Shuttle"]
+        );
+        assert_eq!(
+            sales.outfits,
+            vec!["Hyperdrive", "Meteor Missile", "Meteor Missile Launcher"]
+        );
+    }
+}
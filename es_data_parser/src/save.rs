@@ -0,0 +1,372 @@
+use nom::{branch::alt, character::complete::line_ending, error::context, multi::many0, IResult};
+
+use crate::helpers::{comment_hole, node_at_level};
+use crate::types::{Account, Cargo, Date, Mortgage, Node, SaveGame, SavedShip};
+use crate::DataError;
+
+pub fn parse_save_game<'a>(input: &'a str) -> IResult<&'a str, SaveGame<'a>, DataError<&'a str>> {
+    let (input, nodes) = context(
+        "save game",
+        many0(alt((
+            |input| comment_hole(input).map(|(input, _)| (input, None)),
+            |input| line_ending(input).map(|(input, _)| (input, None)),
+            |input| node_at_level(0, input).map(|(input, node)| (input, Some(node))),
+        ))),
+    )(input)?;
+
+    let mut builder = crate::types::SaveGameBuilder::default();
+    let mut ships = vec![];
+    let mut visited = vec![];
+    let mut visited_planets = vec![];
+    let mut debts = vec![];
+    let mut missions = vec![];
+    let mut others = vec![];
+    for node in nodes.into_iter().flatten() {
+        match *node.tokens.as_slice() {
+            ["pilot", first_name] => {
+                builder.first_name(first_name).last_name("");
+            }
+            ["pilot", first_name, last_name] => {
+                builder.first_name(first_name).last_name(last_name);
+            }
+            ["date", day, month, year] => {
+                if let (Ok(day), Ok(month), Ok(year)) = (day.parse(), month.parse(), year.parse()) {
+                    builder.date(Date { day, month, year });
+                }
+            }
+            ["system", system] => {
+                builder.system(system);
+            }
+            ["planet", planet] => {
+                builder.planet(Some(planet));
+            }
+            ["account"] => {
+                let (account, other_debts) = account(&node.children)?;
+                if let Some(account) = account {
+                    builder.account(account);
+                }
+                debts.extend(other_debts);
+            }
+            ["ship", model] => ships.push(saved_ship(model, node.children)),
+            ["cargo"] => {
+                builder.cargo(cargo(&node.children));
+            }
+            ["visited", system] => visited.push(system),
+            ["visited planet", planet] => visited_planets.push(planet),
+            ["conditions"] => {
+                builder.conditions(
+                    node.children
+                        .iter()
+                        .filter_map(|condition| match *condition.tokens.as_slice() {
+                            [name] => Some((name, 1)),
+                            [name, value] => value.parse().ok().map(|value| (name, value)),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                );
+            }
+            ["reputation with"] | ["reputation", "with"] => {
+                builder.reputation(
+                    node.children
+                        .iter()
+                        .filter_map(|reputation| match *reputation.tokens.as_slice() {
+                            [government, value] => {
+                                value.parse().ok().map(|value| (government, value))
+                            }
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                );
+            }
+            ["mission", ..] => missions.push(node),
+            _ => others.push(node),
+        }
+    }
+    builder
+        .ships(ships)
+        .visited(visited)
+        .visited_planets(visited_planets)
+        .debts(debts)
+        .missions(missions)
+        .others(others);
+
+    builder
+        .build()
+        .map(|save_game| (input, save_game))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("save game"),
+            })
+        })
+}
+
+type Failure<'a> = nom::Err<DataError<&'a str>>;
+type Debts<'a> = Vec<(&'a str, Mortgage)>;
+
+/// The account, if it has credits, and the debts other than the mortgage
+fn account<'a>(nodes: &[Node<'a>]) -> Result<(Option<Account>, Debts<'a>), Failure<'a>> {
+    let mut credits = None;
+    let mut score = 0;
+    let mut mortgage = None;
+    let mut debts = vec![];
+    for node in nodes {
+        match *node.tokens.as_slice() {
+            ["credits", value] => credits = value.parse().ok(),
+            ["score", value] => score = value.parse().unwrap_or_default(),
+            ["mortgage", "Mortgage"] => mortgage = Some(debt(node)?),
+            ["mortgage", kind] => debts.push((kind, debt(node)?)),
+            _ => (),
+        }
+    }
+    Ok((
+        credits.map(|credits| Account {
+            credits,
+            score,
+            mortgage,
+        }),
+        debts,
+    ))
+}
+
+fn debt<'a>(node: &Node<'a>) -> Result<Mortgage, Failure<'a>> {
+    let value = |name: &str| {
+        node.children
+            .iter()
+            .find(|child| child.tokens.first() == Some(&name))
+            .and_then(|child| child.tokens.get(1))
+    };
+    let parsed = || {
+        Some(Mortgage {
+            principal: value("principal")?.parse().ok()?,
+            interest: value("interest")?.parse().ok()?,
+            term: value("term")?.parse().ok()?,
+        })
+    };
+    parsed().ok_or_else(|| {
+        nom::Err::Failure(DataError::DataBuilderError {
+            input: node.tokens[0],
+            error: String::from("a mortgage needs a principal, an interest and a term"),
+            data_type: format!("mortgage {}", node.tokens[1]),
+        })
+    })
+}
+
+fn cargo<'a>(nodes: &[Node<'a>]) -> Cargo<'a> {
+    let mut cargo = Cargo::default();
+    for node in nodes {
+        match *node.tokens.as_slice() {
+            ["commodities"] => cargo.commodities = counted(&node.children),
+            ["outfits"] => cargo.outfits = counted(&node.children),
+            _ => (),
+        }
+    }
+    cargo
+}
+
+fn saved_ship<'a>(model: &'a str, nodes: Vec<Node<'a>>) -> SavedShip<'a> {
+    let mut ship = SavedShip {
+        model,
+        name: None,
+        uuid: None,
+        outfits: vec![],
+        crew: None,
+        fuel: None,
+        shields: None,
+        hull: None,
+        system: None,
+        planet: None,
+        parked: false,
+        others: vec![],
+    };
+    for node in nodes {
+        match *node.tokens.as_slice() {
+            ["name", name] => ship.name = Some(name),
+            ["uuid", uuid] => ship.uuid = Some(uuid),
+            ["outfits"] => ship.outfits = counted(&node.children),
+            ["crew", crew] if crew.parse::<u32>().is_ok() => ship.crew = crew.parse().ok(),
+            ["fuel", fuel] if fuel.parse::<f32>().is_ok() => ship.fuel = fuel.parse().ok(),
+            ["shields", shields] if shields.parse::<f32>().is_ok() => {
+                ship.shields = shields.parse().ok()
+            }
+            ["hull", hull] if hull.parse::<f32>().is_ok() => ship.hull = hull.parse().ok(),
+            ["system", system] => ship.system = Some(system),
+            ["planet", planet] => ship.planet = Some(planet),
+            ["parked"] => ship.parked = true,
+            _ => ship.others.push(node),
+        }
+    }
+    ship
+}
+
+/// items with an optional count, that defaults to 1
+fn counted<'a>(nodes: &[Node<'a>]) -> Vec<(&'a str, u32)> {
+    nodes
+        .iter()
+        .filter_map(|node| match *node.tokens.as_slice() {
+            [name] => Some((name, 1)),
+            [name, count] => count.parse().ok().map(|count| (name, count)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_parse_save_game() {
+        let data = r#"pilot Bob Smith
+date 16 11 3013
+system Rutilicus
+planet "New Boston"
+"travel plan"
+account
+	credits 131000
+	score 400
+	mortgage Mortgage
+		principal 480000
+		interest 0.004
+		term 365
+	mortgage Fine
+		principal 5000
+		interest 0.01
+		term 60
+	history
+		1000
+ship Shuttle
+	name "Bad Sheep"
+	uuid 8c6e2a36-5a87-4c53-9e6e-3a4b1b0e1d2f
+	sprite "ship/shuttle"
+	outfits
+		"Hyperdrive"
+		"X2700 Ion Thruster" 2
+	crew 1
+	fuel 400
+	shields 500
+	hull 600
+	position 10 20
+	system Rutilicus
+	planet "New Boston"
+ship Flivver
+	name Spare
+	parked
+cargo
+	commodities
+		Food 5
+	outfits
+		"Meteor Missile" 10
+# a comment
+visited Rutilicus
+visited Sol
+"visited planet" "New Boston"
+conditions
+	"license: Pilot's"
+	"day" 16
+reputation with
+	Republic 5
+	Pirate -10.5
+mission "First Contact"
+	name "Say hello"
+"#;
+
+        let parsed = dbg!(parse_save_game(data));
+        assert!(parsed.is_ok());
+        let (remaining, save) = parsed.unwrap();
+
+        assert_eq!(remaining, "");
+        assert_eq!(save.first_name, "Bob");
+        assert_eq!(save.last_name, "Smith");
+        assert_eq!(
+            save.date,
+            Date {
+                day: 16,
+                month: 11,
+                year: 3013
+            }
+        );
+        assert_eq!(save.system, "Rutilicus");
+        assert_eq!(save.planet, Some("New Boston"));
+        assert_eq!(
+            save.account,
+            Account {
+                credits: 131000,
+                score: 400,
+                mortgage: Some(Mortgage {
+                    principal: 480000,
+                    interest: 0.004,
+                    term: 365
+                })
+            }
+        );
+        assert_eq!(
+            save.debts,
+            vec![(
+                "Fine",
+                Mortgage {
+                    principal: 5000,
+                    interest: 0.01,
+                    term: 60
+                }
+            )]
+        );
+        assert_eq!(save.ships.len(), 2);
+        let flagship = &save.ships[0];
+        assert_eq!(flagship.model, "Shuttle");
+        assert_eq!(flagship.name, Some("Bad Sheep"));
+        assert_eq!(flagship.uuid, Some("8c6e2a36-5a87-4c53-9e6e-3a4b1b0e1d2f"));
+        assert_eq!(
+            flagship.outfits,
+            vec![("Hyperdrive", 1), ("X2700 Ion Thruster", 2)]
+        );
+        assert_eq!(flagship.crew, Some(1));
+        assert_eq!(flagship.fuel, Some(400.0));
+        assert_eq!(flagship.system, Some("Rutilicus"));
+        assert_eq!(flagship.planet, Some("New Boston"));
+        assert!(!flagship.parked);
+        assert_eq!(flagship.others.len(), 2);
+        assert!(save.ships[1].parked);
+        assert_eq!(
+            save.cargo,
+            Cargo {
+                commodities: vec![("Food", 5)],
+                outfits: vec![("Meteor Missile", 10)],
+            }
+        );
+        assert_eq!(save.visited, vec!["Rutilicus", "Sol"]);
+        assert_eq!(save.visited_planets, vec!["New Boston"]);
+        assert_eq!(save.conditions, vec![("license: Pilot's", 1), ("day", 16)]);
+        assert_eq!(save.reputation, vec![("Republic", 5.0), ("Pirate", -10.5)]);
+        assert_eq!(save.missions.len(), 1);
+        assert_eq!(
+            save.others,
+            vec![Node {
+                tokens: vec!["travel plan"],
+                children: vec![]
+            }]
+        );
+    }
+
+    #[test]
+    fn will_fail_on_invalid_mortgage() {
+        let data = r#"pilot Bob Smith
+date 16 11 3013
+system Rutilicus
+account
+	credits 131000
+	mortgage Mortgage
+		principal lots
+		interest 0.004
+		term 365
+"#;
+
+        match parse_save_game(data) {
+            Err(nom::Err::Failure(DataError::DataBuilderError { data_type, .. })) => {
+                assert_eq!(data_type, "mortgage Mortgage")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, space1},
-    combinator::{opt, peek},
+    combinator::opt,
     error::context,
     multi::{count, many0, many1},
     number::complete::float,
@@ -9,8 +9,9 @@ use nom::{
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
-use crate::types::{Ship, ShipAttributes, ShipWeapon, Sprite};
+use crate::helpers::{eol, indent, integer, resource_path, string};
+use crate::sprite::parse_sprite;
+use crate::types::{Ship, ShipAttributes, ShipWeapon};
 use crate::DataError;
 
 pub fn parse_ship<'a>(input: &'a str) -> IResult<&'a str, Ship<'a>, DataError<&'a str>> {
@@ -21,7 +22,7 @@ pub fn parse_ship<'a>(input: &'a str) -> IResult<&'a str, Ship<'a>, DataError<&'
             space1,
             string,
             opt(preceded(space1, string)),
-            line_ending,
+            eol,
         )),
     )(input)?;
 
@@ -31,7 +32,7 @@ pub fn parse_ship<'a>(input: &'a str) -> IResult<&'a str, Ship<'a>, DataError<&'
     let mut input = input;
     loop {
         crate::parse_item_in_loop!(1, plural, string, input, builder);
-        crate::parse_item_in_loop!(1, sprite, parse_sprite, input, builder);
+        crate::parse_item_in_loop!(1, sprite, |input| parse_sprite(2, input), input, builder);
         crate::parse_item_in_loop!(1, thumbnail, resource_path, input, builder);
         crate::parse_item_in_loop!(1, attributes, parse_ship_attributes, input, builder);
         crate::parse_item_in_loop!(1, outfits, parse_outfits, input, builder);
@@ -127,48 +128,10 @@ pub fn parse_ship<'a>(input: &'a str) -> IResult<&'a str, Ship<'a>, DataError<&'
     })
 }
 
-pub fn parse_sprite<'a>(input: &'a str) -> IResult<&'a str, Sprite<'a>, DataError<&'a str>> {
-    let (input, name) = context("sprite", resource_path)(input)?;
-
-    let peeked: IResult<_, _, (&str, nom::error::ErrorKind)> = peek(preceded(
-        tuple((line_ending, count(indent, 2))),
-        tag("\"frame time\""),
-    ))(input);
-
-    if peeked.is_ok() {
-        let (input, frame_time) = preceded(
-            tuple((line_ending, count(indent, 2), tag("\"frame time\""), space1)),
-            integer,
-        )(input)?;
-        let (input, delay) = preceded(
-            tuple((line_ending, count(indent, 2), tag("\"delay\""), space1)),
-            integer,
-        )(input)?;
-        let (input, random_start_frame) = opt(preceded(
-            tuple((line_ending, count(indent, 2))),
-            tag("\"random start frame\""),
-        ))(input)?;
-
-        let (input, _) = line_ending(input)?;
-
-        Ok((
-            input,
-            Sprite::Sprite {
-                name,
-                frame_time,
-                delay,
-                random_start_frame: random_start_frame.is_some(),
-            },
-        ))
-    } else {
-        Ok((input, Sprite::Simple(name)))
-    }
-}
-
 pub fn parse_ship_attributes<'a>(
     input: &'a str,
 ) -> IResult<&'a str, ShipAttributes<'a>, DataError<&'a str>> {
-    let (input, _) = context("ship attributes", line_ending)(input)?;
+    let (input, _) = context("ship attributes", eol)(input)?;
 
     let mut builder = crate::types::ShipAttributesBuilder::default();
     let mut input = input;
@@ -177,8 +140,8 @@ pub fn parse_ship_attributes<'a>(
             2,
             licenses,
             preceded(
-                line_ending,
-                many1(preceded(count(indent, 3), terminated(string, line_ending)))
+                eol,
+                many1(preceded(count(indent, 3), terminated(string, eol)))
             ),
             input,
             builder
@@ -259,7 +222,7 @@ pub fn parse_ship_attributes<'a>(
 }
 
 pub fn parse_ship_weapon<'a>(input: &'a str) -> IResult<&'a str, ShipWeapon, DataError<&'a str>> {
-    let (input, _) = context("ship attributes - weapon", line_ending)(input)?;
+    let (input, _) = context("ship attributes - weapon", eol)(input)?;
 
     let mut builder = crate::types::ShipWeaponBuilder::default();
     let mut input = input;
@@ -295,7 +258,7 @@ pub fn parse_outfits<'a>(
     input: &'a str,
 ) -> IResult<&'a str, Vec<(&'a str, u32)>, DataError<&'a str>> {
     preceded(
-        line_ending,
+        eol,
         many1(terminated(
             preceded(
                 count(indent, 2),
@@ -367,7 +330,13 @@ mod test {
         assert_eq!(ship.name, "Shuttle");
         assert_eq!(ship.subclass, None);
         assert_eq!(ship.plural, None);
-        assert_eq!(ship.sprite, crate::Sprite::Simple("ship/shuttle"));
+        assert_eq!(
+            ship.sprite,
+            crate::Sprite {
+                name: "ship/shuttle",
+                animation: crate::SpriteAnimation::default()
+            }
+        );
         assert_eq!(ship.thumbnail, "thumbnail/shuttle");
 
         assert_eq!(
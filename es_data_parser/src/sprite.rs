@@ -0,0 +1,252 @@
+use nom::{
+    character::complete::space1,
+    combinator::opt,
+    error::context,
+    multi::count,
+    number::complete::float,
+    sequence::{preceded, terminated},
+    IResult,
+};
+
+use crate::helpers::{eols, indent, integer, resource_path, token};
+use crate::types::{Sprite, SpriteAnimation};
+use crate::DataError;
+
+/// Parse a sprite name and its animation parameters, indented at `level` below it, in any order
+pub fn parse_sprite<'a>(
+    level: usize,
+    input: &'a str,
+) -> IResult<&'a str, Sprite<'a>, DataError<&'a str>> {
+    let (input, name) = context("sprite", terminated(resource_path, eols))(input)?;
+
+    let mut animation = SpriteAnimation::default();
+    let mut input = input;
+    loop {
+        let (remaining, key) =
+            match preceded(count(indent, level), token::<(&str, nom::error::ErrorKind)>)(input) {
+                Ok(parsed) => parsed,
+                Err(_) => break,
+            };
+        let (remaining, _) = opt(space1)(remaining)?;
+        let (remaining, _) = match key {
+            "frame rate" => float(remaining).map(|(remaining, frame_rate)| {
+                animation.frame_rate = frame_rate;
+                (remaining, ())
+            }),
+            // number of game steps, at 60 per second, each frame is shown
+            "frame time" => float(remaining).map(|(remaining, frame_time)| {
+                animation.frame_rate = if frame_time > 0. {
+                    60. / frame_time
+                } else {
+                    0.
+                };
+                (remaining, ())
+            }),
+            "delay" => integer(remaining).map(|(remaining, delay)| {
+                animation.delay = delay;
+                (remaining, ())
+            }),
+            "start frame" => integer(remaining).map(|(remaining, start_frame)| {
+                animation.start_frame = start_frame;
+                (remaining, ())
+            }),
+            "scale" => float(remaining).map(|(remaining, scale)| {
+                animation.scale = scale;
+                (remaining, ())
+            }),
+            "random start frame" => {
+                animation.random_start_frame = true;
+                Ok((remaining, ()))
+            }
+            "no repeat" => {
+                animation.no_repeat = true;
+                Ok((remaining, ()))
+            }
+            "rewind" => {
+                animation.rewind = true;
+                Ok((remaining, ()))
+            }
+            // not an animation parameter, left to the caller
+            _ => break,
+        }
+        .map_err(|error: nom::Err<DataError<&str>>| match error {
+            nom::Err::Error(error) => nom::Err::Failure(error),
+            error => error,
+        })?;
+        let (remaining, _) = context("sprite parameter", eols)(remaining)?;
+        input = remaining;
+    }
+
+    Ok((input, Sprite { name, animation }))
+}
+
+impl SpriteAnimation {
+    /// Index of the frame to show `time` seconds after the animation started, for a sprite with
+    /// `frames` frames
+    ///
+    /// With a random start frame, each instance should add its own random offset to `time`.
+    pub fn frame_at(&self, time: f32, frames: u32) -> u32 {
+        if frames <= 1 {
+            return 0;
+        }
+        let start = self.start_frame % frames;
+        if self.frame_rate <= 0. || time <= 0. {
+            return start;
+        }
+
+        // frames shown in one pass, going back to the first frame when rewinding, counted in 64
+        // bits as they can overflow with large frame counts or delays
+        let frames = u64::from(frames);
+        let pass = if self.rewind {
+            2 * (frames - 1)
+        } else {
+            frames
+        };
+        let elapsed = ((time * self.frame_rate) as u64).saturating_add(u64::from(start));
+        let step = if self.no_repeat {
+            if elapsed >= pass {
+                return if self.rewind { 0 } else { (frames - 1) as u32 };
+            }
+            elapsed
+        } else {
+            let step = elapsed % (pass + u64::from(self.delay));
+            if step >= pass {
+                // waiting before playing again
+                return 0;
+            }
+            step
+        };
+
+        // below `frames`, which fits in 32 bits
+        if step >= frames {
+            (pass - step) as u32
+        } else {
+            step as u32
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_parse_sprite_parameters_in_any_order() {
+        let data = r#""ship/shuttle"
+		"random start frame"
+		"delay" 14
+		"frame time" 4
+		scale 2
+		rewind
+	thumbnail "thumbnail/shuttle"
+"#;
+
+        let parsed = dbg!(parse_sprite(2, data));
+        assert!(parsed.is_ok());
+        let (remaining, sprite) = parsed.unwrap();
+        assert_eq!(remaining, "\tthumbnail \"thumbnail/shuttle\"\n");
+        assert_eq!(
+            sprite,
+            Sprite {
+                name: "ship/shuttle",
+                animation: SpriteAnimation {
+                    frame_rate: 15.,
+                    delay: 14,
+                    start_frame: 0,
+                    random_start_frame: true,
+                    no_repeat: false,
+                    rewind: true,
+                    scale: 2.,
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_simple_sprite() {
+        let parsed = dbg!(parse_sprite(2, "planet/earth\n"));
+        assert_eq!(
+            parsed.unwrap().1,
+            Sprite {
+                name: "planet/earth",
+                animation: SpriteAnimation::default()
+            }
+        );
+    }
+
+    #[test]
+    fn will_fail_on_invalid_parameter() {
+        let parsed = dbg!(parse_sprite(2, "effect/spark\n\t\t\"frame rate\" fast\n"));
+        assert!(matches!(parsed, Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    fn can_find_frame_at_time() {
+        let looping = SpriteAnimation {
+            frame_rate: 10.,
+            delay: 2,
+            ..SpriteAnimation::default()
+        };
+        let frames = (0..10)
+            .map(|tenth| looping.frame_at(tenth as f32 / 10. + 0.01, 4))
+            .collect::<Vec<_>>();
+        assert_eq!(frames, vec![0, 1, 2, 3, 0, 0, 0, 1, 2, 3]);
+
+        let rewinding = SpriteAnimation {
+            frame_rate: 10.,
+            rewind: true,
+            start_frame: 1,
+            ..SpriteAnimation::default()
+        };
+        let frames = (0..8)
+            .map(|tenth| rewinding.frame_at(tenth as f32 / 10. + 0.01, 4))
+            .collect::<Vec<_>>();
+        assert_eq!(frames, vec![1, 2, 3, 2, 1, 0, 1, 2]);
+
+        let once = SpriteAnimation {
+            frame_rate: 10.,
+            no_repeat: true,
+            ..SpriteAnimation::default()
+        };
+        assert_eq!(once.frame_at(0.21, 4), 2);
+        assert_eq!(once.frame_at(60., 4), 3);
+
+        assert_eq!(looping.frame_at(12.3, 1), 0);
+    }
+
+    #[test]
+    fn can_animate_huge_sprites() {
+        let animation = SpriteAnimation {
+            delay: u32::MAX,
+            start_frame: u32::MAX - 1,
+            rewind: true,
+            ..SpriteAnimation::default()
+        };
+        assert_eq!(animation.frame_at(0.5, u32::MAX), u32::MAX - 2);
+        assert_eq!(animation.frame_at(1.0, u32::MAX), u32::MAX - 3);
+
+        let animation = SpriteAnimation {
+            delay: u32::MAX,
+            ..SpriteAnimation::default()
+        };
+        assert_eq!(animation.frame_at(1.0, u32::MAX), 2);
+        assert_eq!(animation.frame_at(1e9, 2), 0);
+    }
+
+    #[test]
+    fn can_animate_forever() {
+        let looping = SpriteAnimation {
+            frame_rate: 10.,
+            delay: 2,
+            start_frame: 1,
+            ..SpriteAnimation::default()
+        };
+        assert_eq!(looping.frame_at(f32::INFINITY, 4), 3);
+
+        let once = SpriteAnimation {
+            no_repeat: true,
+            ..looping
+        };
+        assert_eq!(once.frame_at(f32::INFINITY, 4), 3);
+    }
+}
@@ -1,43 +1,64 @@
 use nom::{
     branch::permutation,
     bytes::complete::tag,
-    character::complete::{line_ending, space1},
+    character::complete::space1,
+    combinator::{map, opt},
     error::{context, ParseError},
     multi::count,
     number::complete::float,
-    sequence::tuple,
+    sequence::{preceded, tuple},
     IResult,
 };
 
-use crate::helpers::{date, indent, integer, string};
-use crate::types::{Account, Date, Mortgage, Start};
-
-pub fn parse_start<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Start<'a>, E> {
-    let (input, _) = tuple((tag("start"), line_ending))(input)?;
-    let (input, (system, planet, date, set, account)) = permutation((
-        parse_system,
-        parse_planet,
-        parse_date,
-        parse_set,
-        parse_account,
-    ))(input)?;
+use crate::helpers::{date, eol, indent, integer, string};
+use crate::types::{Account, Mortgage, Start, StartShip};
+use crate::DataError;
 
-    Ok((
-        input,
-        Start {
-            date,
-            system,
-            planet,
-            account,
-            set,
-        },
-    ))
+pub fn parse_start<'a>(input: &'a str) -> IResult<&'a str, Start<'a>, DataError<&'a str>> {
+    let (input, (_, name, _)) = context(
+        "start tag",
+        tuple((tag("start"), opt(preceded(space1, string)), eol)),
+    )(input)?;
+
+    let mut builder = crate::types::StartBuilder::default();
+    builder.name(name);
+    let mut ships = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, system, string, input, builder);
+        crate::parse_item_in_loop!(1, planet, string, input, builder);
+        crate::parse_item_in_loop!(1, date, date, input, builder);
+        crate::parse_item_in_loop!(1, set, string, input, builder);
+        crate::parse_item_in_loop!(1, account, parse_account, input, builder);
+        crate::push_item_in_loop!(1, "ship", parse_ship, input, ships);
+
+        break;
+    }
+    builder.ships(ships);
+
+    builder
+        .build()
+        .map(|start| (input, start))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("start"),
+            })
+        })
+}
+
+fn parse_ship<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, StartShip<'a>, E> {
+    map(
+        tuple((string, opt(preceded(space1, string)))),
+        |(model, name)| StartShip { model, name },
+    )(input)
 }
 
 fn parse_account<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Account, E> {
-    let (input, _) = tuple((indent, tag("account"), line_ending))(input)?;
+    let (input, _) = eol(input)?;
     let (input, (credits, score, mortgage)) =
-        permutation((parse_credits, parse_score, parse_mortgage))(input)?;
+        permutation((parse_credits, parse_score, opt(parse_mortgage)))(input)?;
 
     Ok((
         input,
@@ -56,7 +77,7 @@ fn parse_mortgage<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str
         tag("mortgage"),
         space1,
         tag("Mortgage"),
-        line_ending,
+        eol,
     ))(input)?;
 
     let (input, (principal, interest, term)) =
@@ -72,11 +93,6 @@ fn parse_mortgage<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str
     ))
 }
 
-crate::parse_item_with_indent!(1, parse_system, system, string, &'a str);
-crate::parse_item_with_indent!(1, parse_set, set, string, &'a str);
-crate::parse_item_with_indent!(1, parse_planet, planet, string, &'a str);
-crate::parse_item_with_indent!(1, parse_date, date, date, Date);
-
 crate::parse_item_with_indent!(2, parse_credits, credits, integer, u64);
 crate::parse_item_with_indent!(2, parse_score, score, integer, u32);
 
@@ -87,8 +103,7 @@ crate::parse_item_with_indent!(3, parse_term, term, integer, u16);
 #[cfg(test)]
 mod test {
     use super::*;
-
-    use nom::error::VerboseError;
+    use crate::types::Date;
 
     #[test]
     fn can_parse_start() {
@@ -106,9 +121,11 @@ mod test {
             term 365
 "#;
 
-        let parsed = dbg!(parse_start::<VerboseError<&str>>(&data));
+        let parsed = dbg!(parse_start(&data));
         assert!(parsed.is_ok());
         let start = parsed.unwrap().1;
+        assert_eq!(start.name, None);
+        assert_eq!(start.ships, vec![]);
         assert_eq!(start.system, String::from("my system"));
         assert_eq!(start.planet, String::from("this planet"));
         assert_eq!(start.set, String::from("my license"));
@@ -125,12 +142,89 @@ mod test {
             Account {
                 credits: 5000,
                 score: 100,
-                mortgage: Mortgage {
+                mortgage: Some(Mortgage {
                     principal: 33333,
                     interest: 0.005,
                     term: 365
-                }
+                })
             }
         );
     }
+
+    #[test]
+    fn can_parse_named_start_with_ships() {
+        let data = r#"start "Pirate"
+	ship "Shuttle" "Bad Sheep"
+	system Rutilicus
+	planet "New Boston"
+	date 16 11 3013
+	set "license: Pilot's"
+	account
+		credits 1000
+		score 0
+	ship Sparrow
+"#;
+
+        let parsed = parse_start(data);
+        assert!(parsed.is_ok());
+        let (remaining, start) = parsed.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(start.name, Some("Pirate"));
+        assert_eq!(
+            start.ships,
+            vec![
+                StartShip {
+                    model: "Shuttle",
+                    name: Some("Bad Sheep")
+                },
+                StartShip {
+                    model: "Sparrow",
+                    name: None
+                },
+            ]
+        );
+        assert_eq!(start.account.mortgage, None);
+    }
+
+    #[test]
+    fn can_parse_start_keys_in_any_order() {
+        let data = r#"start
+	account
+		score 0
+		credits 1000
+	system Rutilicus
+	ship "Shuttle" "Bad Sheep"
+	date 16 11 3013
+	ship Sparrow
+	set "license: Pilot's"
+	planet "New Boston"
+"#;
+
+        let (remaining, start) = parse_start(data).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(start.system, "Rutilicus");
+        assert_eq!(start.planet, "New Boston");
+        assert_eq!(start.account.credits, 1000);
+        assert_eq!(
+            start.ships,
+            vec![
+                StartShip {
+                    model: "Shuttle",
+                    name: Some("Bad Sheep")
+                },
+                StartShip {
+                    model: "Sparrow",
+                    name: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn will_fail_on_overflowing_date() {
+        let data = "start\n\tdate 01 07 99999999999\n";
+
+        let parsed = dbg!(parse_start(data));
+        assert!(parsed.is_err());
+    }
 }
@@ -0,0 +1,76 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    error::{context, ParseError},
+    multi::many0,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::helpers::{eols, indent, node_at_level, string};
+use crate::types::Substitution;
+
+pub fn parse_substitutions<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<Substitution<'a>>, E> {
+    let (input, _) = context("substitutions tag", tuple((tag("substitutions"), eols)))(input)?;
+
+    context("substitutions", many0(parse_substitution))(input)
+}
+
+fn parse_substitution<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Substitution<'a>, E> {
+    let (input, (_, key, _, value, _)) = tuple((indent, string, space1, string, eols))(input)?;
+    let (input, conditions) = many0(|input| node_at_level(2, input))(input)?;
+
+    Ok((
+        input,
+        Substitution {
+            key,
+            value,
+            conditions,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    use crate::types::Node;
+
+    #[test]
+    fn can_parse_substitutions() {
+        let data = r#"substitutions
+	"<fuel>" "fuel"
+	"<captain>" "Captain"
+		"has flagship"
+"#;
+
+        let parsed = dbg!(parse_substitutions::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let substitutions = parsed.unwrap().1;
+
+        assert_eq!(
+            substitutions,
+            vec![
+                Substitution {
+                    key: "<fuel>",
+                    value: "fuel",
+                    conditions: vec![]
+                },
+                Substitution {
+                    key: "<captain>",
+                    value: "Captain",
+                    conditions: vec![Node {
+                        tokens: vec!["has flagship"],
+                        children: vec![]
+                    }]
+                }
+            ]
+        );
+    }
+}
@@ -1,101 +1,124 @@
 use nom::{
-    branch::permutation,
+    branch::alt,
     bytes::complete::tag,
-    character::complete::{line_ending, space1},
-    combinator::opt,
+    character::complete::space1,
+    combinator::{map, opt, peek},
     error::{context, ParseError},
-    multi::count,
-    multi::many0,
+    multi::{count, separated_list},
     number::complete::{double, float},
-    sequence::tuple,
+    sequence::{preceded, tuple},
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
-use crate::types::{Asteroids, Fleet, Minables, Position, System, SystemObject, Trade};
+use crate::helpers::{eol, eols, indent, integer, resource_path, string};
+use crate::sprite::parse_sprite;
+use crate::types::{
+    ArrivalDistance, Asteroids, Fleet, Minables, Object, Planet, Position, Raid, Ramscoop, System,
+    SystemHazard, SystemObject, Trade,
+};
+use crate::DataError;
 
-pub fn parse_system<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, System<'a>, E> {
-    let (input, (_, _, name, _)) = context(
-        "systme tag",
-        tuple((tag("system"), space1, string, line_ending)),
-    )(input)?;
-    let (
-        input,
-        (
-            pos,
-            government,
-            habitable,
-            belt,
-            haze,
-            links,
-            asteroids,
-            minables,
-            trades,
-            fleets,
-            objects,
-        ),
-    ) = context(
-        "system fields",
-        permutation((
-            parse_pos,
-            parse_government,
-            parse_habitable,
-            opt(parse_belt),
-            opt(parse_haze),
-            many0(parse_link),
-            many0(parse_asteroids),
-            many0(parse_minables),
-            many0(parse_trades),
-            many0(parse_fleet),
-            many0(parse_object),
-        )),
-    )(input)?;
+pub fn parse_system<'a>(input: &'a str) -> IResult<&'a str, System<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) =
+        context("system tag", tuple((tag("system"), space1, string, eols)))(input)?;
 
-    Ok((
-        input,
-        System {
-            name,
-            pos,
-            government,
-            habitable,
-            asteroids,
-            minables,
-            haze,
-            belt,
-            fleets,
-            links,
-            trades,
-            objects,
-        },
-    ))
+    let mut builder = crate::types::SystemBuilder::default();
+    builder.name(name);
+    let mut links = vec![];
+    let mut asteroids = vec![];
+    let mut minables = vec![];
+    let mut trades = vec![];
+    let mut fleets = vec![];
+    let mut raids = vec![];
+    let mut hazards = vec![];
+    let mut objects = vec![];
+    let mut others = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, pos, parse_position, input, builder);
+        crate::parse_item_in_loop!(1, government, string, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            attributes,
+            separated_list(space1, string),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, music, resource_path, input, builder);
+        crate::parse_item_in_loop!(1, arrival, parse_arrival_distance, input, builder);
+        crate::parse_item_in_loop!(1, departure, parse_arrival_distance, input, builder);
+        crate::parse_item_in_loop!(1, habitable, float, input, builder);
+        crate::parse_item_in_loop!(1, belt, integer::<u32, _>, input, builder);
+        crate::parse_item_in_loop!(1, haze, resource_path, input, builder);
+        crate::parse_item_in_loop!(1, ramscoop, parse_ramscoop, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            invisible_fence,
+            "\"invisible fence\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, jump_range, "\"jump range\"", float, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            starfield_density,
+            "\"starfield density\"",
+            float,
+            input,
+            builder
+        );
+        crate::push_item_in_loop!(1, "link", string, input, links);
+        crate::push_item_in_loop!(1, "asteroids", parse_asteroids, input, asteroids);
+        crate::push_item_in_loop!(1, "minables", parse_minables, input, minables);
+        crate::push_item_in_loop!(1, "trade", parse_trade, input, trades);
+        crate::push_item_in_loop!(1, "fleet", parse_fleet, input, fleets);
+        crate::push_item_in_loop!(1, "raid", parse_raid, input, raids);
+        crate::push_item_in_loop!(1, "hazard", parse_hazard, input, hazards);
+        if peek::<_, _, (&str, nom::error::ErrorKind), _>(tuple((indent, tag("object"))))(input)
+            .is_ok()
+        {
+            let (remaining, object) = parse_object_at_level(0, input)?;
+            input = remaining;
+            objects.push(object);
+            continue;
+        }
+        crate::push_other_in_loop!(1, input, others);
+
+        break;
+    }
+    builder
+        .links(links)
+        .asteroids(asteroids)
+        .minables(minables)
+        .trades(trades)
+        .fleets(fleets)
+        .raids(raids)
+        .hazards(hazards)
+        .objects(objects)
+        .others(others);
+
+    builder
+        .build()
+        .map(|system| (input, system))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("system"),
+            })
+        })
 }
 
-fn parse_pos<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Position, E> {
-    let (input, (_, _, _, x, _, y, _)) = tuple((
-        indent,
-        tag("pos"),
-        space1,
-        double,
-        space1,
-        double,
-        line_ending,
-    ))(input)?;
+fn parse_position<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Position, E> {
+    let (input, (x, _, y)) = tuple((double, space1, double))(input)?;
 
     Ok((input, Position { x, y }))
 }
 
-fn parse_asteroids<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Asteroids, E> {
-    let (input, (_, _, _, name, _, first_value, _, second_value, _)) = tuple((
-        indent,
-        tag("asteroids"),
-        space1,
-        string,
-        space1,
-        integer,
-        space1,
-        float,
-        line_ending,
-    ))(input)?;
+fn parse_asteroids<'a>(input: &'a str) -> IResult<&'a str, Asteroids<'a>, DataError<&'a str>> {
+    let (input, (name, _, first_value, _, second_value)) =
+        tuple((string, space1, integer, space1, float))(input)?;
     Ok((
         input,
         Asteroids {
@@ -105,18 +128,9 @@ fn parse_asteroids<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a st
         },
     ))
 }
-fn parse_minables<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Minables, E> {
-    let (input, (_, _, _, name, _, first_value, _, second_value, _)) = tuple((
-        indent,
-        tag("minables"),
-        space1,
-        string,
-        space1,
-        integer,
-        space1,
-        float,
-        line_ending,
-    ))(input)?;
+fn parse_minables<'a>(input: &'a str) -> IResult<&'a str, Minables<'a>, DataError<&'a str>> {
+    let (input, (name, _, first_value, _, second_value)) =
+        tuple((string, space1, integer, space1, float))(input)?;
     Ok((
         input,
         Minables {
@@ -126,87 +140,194 @@ fn parse_minables<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str
         },
     ))
 }
-fn parse_trades<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Trade, E> {
-    let (input, (_, _, _, name, _, price, _)) = tuple((
-        indent,
-        tag("trade"),
-        space1,
-        string,
-        space1,
-        integer,
-        line_ending,
-    ))(input)?;
+fn parse_trade<'a>(input: &'a str) -> IResult<&'a str, Trade<'a>, DataError<&'a str>> {
+    let (input, (name, _, price)) = tuple((string, space1, integer))(input)?;
     Ok((input, Trade { name, price }))
 }
-fn parse_fleet<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Fleet, E> {
-    let (input, (_, _, _, kind, _, count, _)) = tuple((
-        indent,
-        tag("fleet"),
-        space1,
+fn parse_fleet<'a>(input: &'a str) -> IResult<&'a str, Fleet<'a>, DataError<&'a str>> {
+    let (input, (kind, _, count)) = tuple((string, space1, integer))(input)?;
+    Ok((input, Fleet { kind, count }))
+}
+fn parse_raid<'a>(input: &'a str) -> IResult<&'a str, Raid<'a>, DataError<&'a str>> {
+    let (input, (fleet, minimum_attraction, maximum_attraction)) = tuple((
         string,
-        space1,
-        integer,
-        line_ending,
+        opt(preceded(space1, float)),
+        opt(preceded(space1, float)),
     ))(input)?;
-    Ok((input, Fleet { kind, count }))
+    Ok((
+        input,
+        Raid {
+            fleet,
+            minimum_attraction,
+            maximum_attraction,
+        },
+    ))
+}
+fn parse_hazard<'a>(input: &'a str) -> IResult<&'a str, SystemHazard<'a>, DataError<&'a str>> {
+    let (input, (name, _, period)) = tuple((string, space1, integer))(input)?;
+    Ok((input, SystemHazard { name, period }))
 }
-fn parse_object<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, SystemObject, E> {
-    parse_object_at_level(0, input)
+
+fn parse_arrival_distance(input: &str) -> IResult<&str, ArrivalDistance, DataError<&str>> {
+    alt((
+        map(float, |distance| ArrivalDistance {
+            link: Some(distance),
+            jump: Some(distance),
+        }),
+        |input| {
+            let (input, _) = eol(input)?;
+
+            let mut builder = crate::types::ArrivalDistanceBuilder::default();
+            let mut input = input;
+            loop {
+                crate::parse_item_in_loop!(2, link, float, input, builder);
+                crate::parse_item_in_loop!(2, jump, float, input, builder);
+
+                break;
+            }
+
+            builder
+                .build()
+                .map(|arrival_distance| (input, arrival_distance))
+                .map_err(|error| {
+                    nom::Err::Failure(DataError::DataBuilderError {
+                        input,
+                        error,
+                        data_type: String::from("arrival distance"),
+                    })
+                })
+        },
+    ))(input)
 }
-fn parse_object_at_level<'a, E: ParseError<&'a str>>(
+
+fn parse_ramscoop(input: &str) -> IResult<&str, Ramscoop, DataError<&str>> {
+    let (input, _) = context("ramscoop", eol)(input)?;
+
+    let mut builder = crate::types::RamscoopBuilder::default();
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(
+            2,
+            universal,
+            |input| integer(input).map(|(r, v): (_, u32)| (r, v != 0)),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(2, addend, float, input, builder);
+        crate::parse_item_in_loop!(2, multiplier, float, input, builder);
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|ramscoop| (input, ramscoop))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("ramscoop"),
+            })
+        })
+}
+
+fn parse_object_at_level<'a>(
     level: usize,
     input: &'a str,
-) -> IResult<&'a str, SystemObject, E> {
-    // eprintln!("--> {} - {:?}", level, input);
+) -> IResult<&'a str, SystemObject<'a>, DataError<&'a str>> {
     let (input, (_, _, name, _)) = context(
         "object tag",
         tuple((
             count(indent, level + 1),
             tag("object"),
-            opt(tuple((space1, string))),
-            line_ending,
-        )),
-    )(input)?;
-    let (input, (sprite, distance, period, offset, objects)) = context(
-        "object fields",
-        permutation((
-            |input| opt(tuple((count(indent, level + 1), parse_sprite)))(input),
-            |input| opt(tuple((count(indent, level + 1), parse_distance)))(input),
-            |input| tuple((count(indent, level + 1), parse_period))(input),
-            |input| opt(tuple((count(indent, level + 1), parse_offset)))(input),
-            |input| many0(|input| parse_object_at_level(level + 1, input))(input),
+            opt(preceded(space1, string)),
+            eols,
         )),
     )(input)?;
 
-    Ok((
-        input,
-        SystemObject {
-            name: name.map(|name| name.1),
-            sprite: sprite.map(|sprite| sprite.1),
-            distance: distance.map(|distance| distance.1),
-            offset: offset.map(|offset| offset.1),
-            period: period.1,
-            objects,
-        },
-    ))
+    let mut builder = crate::types::SystemObjectBuilder::default();
+    builder.name(name);
+    let mut hazards = vec![];
+    let mut objects = vec![];
+    let mut others = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(
+            level + 2,
+            sprite,
+            |input| parse_sprite(level + 3, input),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(level + 2, distance, float, input, builder);
+        crate::parse_item_in_loop!(level + 2, period, float, input, builder);
+        crate::parse_item_in_loop!(level + 2, offset, float, input, builder);
+        crate::push_item_in_loop!(level + 2, "hazard", parse_hazard, input, hazards);
+        if peek::<_, _, (&str, nom::error::ErrorKind), _>(tuple((
+            count(indent, level + 2),
+            tag("object"),
+        )))(input)
+        .is_ok()
+        {
+            let (remaining, object) = parse_object_at_level(level + 1, input)?;
+            input = remaining;
+            objects.push(object);
+            continue;
+        }
+        crate::push_other_in_loop!(level + 2, input, others);
+
+        break;
+    }
+    builder.hazards(hazards).objects(objects).others(others);
+
+    builder
+        .build()
+        .map(|object| (input, object))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("system object"),
+            })
+        })
 }
 
-crate::parse_item_with_indent!(1, parse_government, government, string, &'a str);
-crate::parse_item_with_indent!(1, parse_habitable, habitable, float, f32);
-crate::parse_item_with_indent!(1, parse_belt, belt, integer, u32);
-crate::parse_item_with_indent!(1, parse_link, link, string, &'a str);
-crate::parse_item_with_indent!(1, parse_haze, haze, resource_path, &'a str);
+impl<'a> SystemObject<'a> {
+    /// Find the definition of the planet this object is, if it is named
+    pub fn planet<'b>(&self, objects: &'b [Object<'a>]) -> Option<&'b Planet<'a>> {
+        let name = self.name?;
+        objects.iter().rev().find_map(|object| match object {
+            Object::Planet(planet) if planet.name == name => Some(planet),
+            _ => None,
+        })
+    }
+}
 
-crate::parse_item_with_indent!(1, parse_sprite, sprite, resource_path, &'a str);
-crate::parse_item_with_indent!(1, parse_distance, distance, float, f32);
-crate::parse_item_with_indent!(1, parse_offset, offset, float, f32);
-crate::parse_item_with_indent!(1, parse_period, period, float, f32);
+impl<'a> System<'a> {
+    /// Definitions of all the planets in this system, including moons
+    pub fn planets<'b>(&self, objects: &'b [Object<'a>]) -> Vec<&'b Planet<'a>> {
+        fn add_planets<'a, 'b>(
+            system_objects: &[SystemObject<'a>],
+            objects: &'b [Object<'a>],
+            planets: &mut Vec<&'b Planet<'a>>,
+        ) {
+            for system_object in system_objects {
+                if let Some(planet) = system_object.planet(objects) {
+                    planets.push(planet);
+                }
+                add_planets(&system_object.objects, objects, planets);
+            }
+        }
+        let mut planets = vec![];
+        add_planets(&self.objects, objects, &mut planets);
+        planets
+    }
+}
 
 #[cfg(test)]
 mod test {
     use super::*;
-
-    use nom::error::VerboseError;
+    use crate::types::{Node, Sprite, SpriteAnimation};
 
     #[test]
     fn can_parse_system() {
@@ -221,6 +342,7 @@ mod test {
     minables lead 11 10
     trade Goods 100
     fleet "Small Vessel" 100
+    hazard "Ion Storm" 10000
     object
         sprite planet/visual-planet
         distance 1811.79
@@ -236,7 +358,7 @@ mod test {
         offset 180
 "#;
 
-        let parsed = dbg!(parse_system::<VerboseError<&str>>(&data));
+        let parsed = dbg!(parse_system(data));
         assert!(parsed.is_ok());
         let system = parsed.unwrap().1;
 
@@ -289,34 +411,185 @@ mod test {
                 count: 100
             }]
         );
+        assert_eq!(
+            system.hazards,
+            vec![SystemHazard {
+                name: "Ion Storm",
+                period: 10000
+            }]
+        );
 
         assert_eq!(
             system.objects,
             vec![
                 SystemObject {
                     name: None,
-                    sprite: Some("planet/visual-planet"),
+                    sprite: Some(Sprite {
+                        name: "planet/visual-planet",
+                        animation: SpriteAnimation::default(),
+                    }),
                     distance: Some(1811.79),
                     period: 1129.48,
                     offset: None,
+                    hazards: vec![],
                     objects: vec![SystemObject {
                         name: Some("Moon"),
-                        sprite: Some("moon/nice-moon"),
+                        sprite: Some(Sprite {
+                            name: "moon/nice-moon",
+                            animation: SpriteAnimation::default(),
+                        }),
                         distance: Some(229.0),
                         period: 12.994,
                         offset: None,
-                        objects: vec![]
-                    }]
+                        hazards: vec![],
+                        objects: vec![],
+                        others: vec![],
+                    }],
+                    others: vec![],
                 },
                 SystemObject {
                     name: None,
-                    sprite: Some("star/k5"),
+                    sprite: Some(Sprite {
+                        name: "star/k5",
+                        animation: SpriteAnimation::default(),
+                    }),
                     distance: Some(49.335),
                     period: 18.0618,
                     offset: Some(180.0),
-                    objects: vec![]
+                    hazards: vec![],
+                    objects: vec![],
+                    others: vec![],
                 }
             ]
         );
     }
+
+    #[test]
+    fn can_parse_complete_system() {
+        let data = r#"system "Other System"
+	pos 10 -20
+	government Republic
+	attributes "core" "urban"
+	music "ambient/sol"
+	arrival 1000
+	departure
+		link 500
+		jump 1500
+	habitable 625
+	ramscoop
+		universal 0
+		multiplier 0.5
+	"invisible fence" 15000
+	"jump range" 100
+	"starfield density" 2
+	link "My System"
+	fleet "Small Vessel" 100
+	raid "Pirate Raid" 50 200
+	link "Third System"
+	"no raids"
+	object Star
+		sprite star/g5
+			scale 2
+		period 10
+		hazard "Solar Flare" 300
+		"something new" 3
+	"something else"
+		with children
+"#;
+
+        let parsed = dbg!(parse_system(data));
+        assert!(parsed.is_ok());
+        let system = parsed.unwrap().1;
+
+        assert_eq!(system.attributes, vec!["core", "urban"]);
+        assert_eq!(system.music, Some("ambient/sol"));
+        assert_eq!(
+            system.arrival,
+            Some(ArrivalDistance {
+                link: Some(1000.0),
+                jump: Some(1000.0)
+            })
+        );
+        assert_eq!(
+            system.departure,
+            Some(ArrivalDistance {
+                link: Some(500.0),
+                jump: Some(1500.0)
+            })
+        );
+        assert_eq!(
+            system.ramscoop,
+            Some(Ramscoop {
+                universal: Some(false),
+                addend: None,
+                multiplier: Some(0.5)
+            })
+        );
+        assert_eq!(system.invisible_fence, Some(15000.0));
+        assert_eq!(system.jump_range, Some(100.0));
+        assert_eq!(system.starfield_density, Some(2.0));
+        assert_eq!(system.links, vec!["My System", "Third System"]);
+        assert_eq!(
+            system.raids,
+            vec![Raid {
+                fleet: "Pirate Raid",
+                minimum_attraction: Some(50.0),
+                maximum_attraction: Some(200.0)
+            }]
+        );
+        assert_eq!(
+            system.objects,
+            vec![SystemObject {
+                name: Some("Star"),
+                sprite: Some(Sprite {
+                    name: "star/g5",
+                    animation: SpriteAnimation {
+                        scale: 2.,
+                        ..SpriteAnimation::default()
+                    },
+                }),
+                distance: None,
+                period: 10.0,
+                offset: None,
+                hazards: vec![SystemHazard {
+                    name: "Solar Flare",
+                    period: 300
+                }],
+                objects: vec![],
+                others: vec![Node {
+                    tokens: vec!["something new", "3"],
+                    children: vec![]
+                }],
+            }]
+        );
+        assert_eq!(
+            system.others,
+            vec![
+                Node {
+                    tokens: vec!["no raids"],
+                    children: vec![]
+                },
+                Node {
+                    tokens: vec!["something else"],
+                    children: vec![Node {
+                        tokens: vec!["with", "children"],
+                        children: vec![]
+                    }]
+                }
+            ]
+        );
+    }
+    #[test]
+    fn can_default_period_and_fail_on_broken_objects() {
+        let data =
+            "system Still\n\tpos 0 0\n\tgovernment Uninhabited\n\thabitable 1\n\tobject Rock\n";
+        let system = parse_system(data).unwrap().1;
+        assert_eq!(system.objects.len(), 1);
+        assert_eq!(system.objects[0].period, 0.);
+        assert_eq!(system.others, vec![]);
+
+        // not kept as an unknown field
+        let data = format!("{}\t\tperiod soon\n", data);
+        assert!(parse_system(&data).is_err());
+    }
 }
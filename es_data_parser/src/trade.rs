@@ -0,0 +1,117 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    combinator::opt,
+    error::{context, ParseError},
+    multi::{count, many0},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::helpers::{eol, eols, indent, integer, string};
+use crate::types::{Commodities, Commodity};
+
+pub fn parse_trade<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Commodities<'a>, E> {
+    let (input, _) = context("trade tag", tuple((tag("trade"), eol)))(input)?;
+    let (input, commodities) = context("trade commodities", many0(parse_commodity))(input)?;
+
+    Ok((input, Commodities { commodities }))
+}
+
+fn parse_commodity<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Commodity<'a>, E> {
+    let (input, (_, _, _, name, prices, _)) = context(
+        "commodity",
+        tuple((
+            indent,
+            tag("commodity"),
+            space1,
+            string,
+            opt(tuple((space1, integer, space1, integer))),
+            eols,
+        )),
+    )(input)?;
+    let (input, items) = context(
+        "commodity items",
+        many0(terminated(preceded(count(indent, 2), string), eols)),
+    )(input)?;
+
+    Ok((
+        input,
+        Commodity {
+            name,
+            low: prices.map(|(_, low, _, _)| low),
+            high: prices.map(|(_, _, _, high)| high),
+            items,
+        },
+    ))
+}
+
+impl<'a> Commodities<'a> {
+    /// Special commodities, that don't have a price range and are only used by missions
+    pub fn specials(&self) -> impl Iterator<Item = &Commodity<'a>> {
+        self.commodities
+            .iter()
+            .filter(|commodity| commodity.low.is_none())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_trade() {
+        let data = r#"trade
+	commodity "Food" 100 600
+		"animal products"
+		bread
+	commodity Clothing 140 460
+		"fashion"
+
+	commodity "Special"
+		"artwork"
+		"prototype weapons"
+"#;
+
+        let parsed = dbg!(parse_trade::<VerboseError<&str>>(data));
+        assert!(parsed.is_ok());
+        let trade = parsed.unwrap().1;
+
+        assert_eq!(
+            trade.commodities,
+            vec![
+                Commodity {
+                    name: "Food",
+                    low: Some(100),
+                    high: Some(600),
+                    items: vec!["animal products", "bread"],
+                },
+                Commodity {
+                    name: "Clothing",
+                    low: Some(140),
+                    high: Some(460),
+                    items: vec!["fashion"],
+                },
+                Commodity {
+                    name: "Special",
+                    low: None,
+                    high: None,
+                    items: vec!["artwork", "prototype weapons"],
+                }
+            ]
+        );
+        assert_eq!(
+            trade
+                .specials()
+                .map(|commodity| commodity.name)
+                .collect::<Vec<_>>(),
+            vec!["Special"]
+        );
+    }
+}
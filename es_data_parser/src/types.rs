@@ -20,8 +20,8 @@ pub struct Account {
     pub credits: u64,
     /// his credit score
     pub score: u32,
-    /// his current mortgage
-    pub mortgage: Mortgage,
+    /// his current mortgage, if he has one
+    pub mortgage: Option<Mortgage>,
 }
 
 /// A date
@@ -62,6 +62,9 @@ pub struct Tribute<'a> {
 #[derive(Debug, PartialEq, Clone, Builder)]
 #[builder(setter(into))]
 pub struct Start<'a> {
+    /// name of the start scenario, when there are several
+    #[builder(default)]
+    pub name: Option<&'a str>,
     /// start date
     pub date: Date,
     /// start system
@@ -72,6 +75,18 @@ pub struct Start<'a> {
     pub account: Account,
     /// start set
     pub set: &'a str,
+    /// ships the player starts with, the first one being the flagship
+    #[builder(default)]
+    pub ships: Vec<StartShip<'a>>,
+}
+
+/// A ship the player starts with
+#[derive(Debug, PartialEq, Clone)]
+pub struct StartShip<'a> {
+    /// model of the ship
+    pub model: &'a str,
+    /// name given to the ship, the model name otherwise
+    pub name: Option<&'a str>,
 }
 
 /// A planet
@@ -115,6 +130,9 @@ pub struct Planet<'a> {
     /// required reputation with planet faction to land
     #[builder(default)]
     pub required_reputation: Option<f32>,
+    /// if this planet is a wormhole, linking the systems it is in
+    #[builder(default)]
+    pub wormhole: bool,
 }
 
 /// A position
@@ -173,22 +191,85 @@ pub struct Trade<'a> {
     pub price: u32,
 }
 
+/// A hazard that can happen in a system
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct SystemHazard<'a> {
+    /// name of the hazard
+    pub name: &'a str,
+    /// period (?)
+    pub period: u32,
+}
+
+/// Distance from the center of a system at which ships arrive or depart
+#[derive(Debug, PartialEq, Clone, Copy, Builder)]
+#[builder(setter(into))]
+pub struct ArrivalDistance {
+    /// when travelling through a hyperspace link
+    #[builder(default)]
+    pub link: Option<f32>,
+    /// when using a jump drive
+    #[builder(default)]
+    pub jump: Option<f32>,
+}
+
+/// How a system changes the fuel collected by ramscoops
+#[derive(Debug, PartialEq, Clone, Copy, Builder)]
+#[builder(setter(into))]
+pub struct Ramscoop {
+    /// if ramscoops work in this system even without a star
+    #[builder(default)]
+    pub universal: Option<bool>,
+    /// fuel added to what is collected
+    #[builder(default)]
+    pub addend: Option<f32>,
+    /// factor applied to what is collected
+    #[builder(default)]
+    pub multiplier: Option<f32>,
+}
+
+/// A fleet that can raid a system
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Raid<'a> {
+    /// kind of the fleet
+    pub fleet: &'a str,
+    /// minimum attraction of the player for the raid to happen
+    #[builder(default)]
+    pub minimum_attraction: Option<f32>,
+    /// attraction of the player at which the raid is the strongest
+    #[builder(default)]
+    pub maximum_attraction: Option<f32>,
+}
+
 /// An object in a system
 #[derive(Debug, PartialEq, Clone, Builder)]
 #[builder(setter(into))]
 pub struct SystemObject<'a> {
-    /// it's name
+    /// it's name, that of the planet it is
+    #[builder(default)]
     pub name: Option<&'a str>,
     /// it's sprite
-    pub sprite: Option<&'a str>,
+    #[builder(default)]
+    pub sprite: Option<Sprite<'a>>,
     /// distance
+    #[builder(default)]
     pub distance: Option<f32>,
-    /// period
+    /// period, 0 for an object that doesn't move
+    #[builder(default)]
     pub period: f32,
     /// offset
+    #[builder(default)]
     pub offset: Option<f32>,
+    /// hazards that can happen near this object
+    #[builder(default)]
+    pub hazards: Vec<SystemHazard<'a>>,
     /// related objects
+    #[builder(default)]
     pub objects: Vec<SystemObject<'a>>,
+    /// other fields, kept as is
+    #[builder(default)]
+    pub others: Vec<Node<'a>>,
 }
 
 /// A system
@@ -201,24 +282,65 @@ pub struct System<'a> {
     pub pos: Position,
     /// it's government
     pub government: &'a str,
+    /// attributes of the system
+    #[builder(default)]
+    pub attributes: Vec<&'a str>,
+    /// music to play in the system
+    #[builder(default)]
+    pub music: Option<&'a str>,
+    /// where ships arrive in the system
+    #[builder(default)]
+    pub arrival: Option<ArrivalDistance>,
+    /// where ships can depart from the system
+    #[builder(default)]
+    pub departure: Option<ArrivalDistance>,
     /// habitable (?)
     pub habitable: f32,
     /// belt (?)
+    #[builder(default)]
     pub belt: Option<u32>,
     /// haze type
+    #[builder(default)]
     pub haze: Option<&'a str>,
+    /// how ramscoops work in this system
+    #[builder(default)]
+    pub ramscoop: Option<Ramscoop>,
+    /// distance after which ships can't go further from the center
+    #[builder(default)]
+    pub invisible_fence: Option<f32>,
+    /// range of jump drives from this system
+    #[builder(default)]
+    pub jump_range: Option<f32>,
+    /// density of the background stars
+    #[builder(default)]
+    pub starfield_density: Option<f32>,
     /// links to other systems
+    #[builder(default)]
     pub links: Vec<&'a str>,
     /// asteroids present in the system
+    #[builder(default)]
     pub asteroids: Vec<Asteroids<'a>>,
     /// minables present in the system
+    #[builder(default)]
     pub minables: Vec<Minables<'a>>,
     /// trade goods that are sold here
+    #[builder(default)]
     pub trades: Vec<Trade<'a>>,
     /// fleets present in the system
+    #[builder(default)]
     pub fleets: Vec<Fleet<'a>>,
+    /// fleets that can raid the system
+    #[builder(default)]
+    pub raids: Vec<Raid<'a>>,
+    /// hazards that can happen in the system
+    #[builder(default)]
+    pub hazards: Vec<SystemHazard<'a>>,
     /// objects present in the system
+    #[builder(default)]
     pub objects: Vec<SystemObject<'a>>,
+    /// other fields, kept as is
+    #[builder(default)]
+    pub others: Vec<Node<'a>>,
 }
 
 /// weapon of a ship (?)
@@ -281,22 +403,46 @@ pub struct ShipAttributes<'a> {
     pub weapon: ShipWeapon,
 }
 
-/// a sprite
-#[derive(Debug, PartialEq, Clone)]
-pub enum Sprite<'a> {
-    /// Complex sprite with multiple frames
-    Sprite {
-        /// name of the sprite
-        name: &'a str,
-        /// (?)
-        frame_time: u32,
-        /// (?)
-        delay: u32,
-        /// (?)
-        random_start_frame: bool,
-    },
-    /// Simple sprite
-    Simple(&'a str),
+/// a sprite, with how it is animated
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sprite<'a> {
+    /// name of the sprite, without the frame suffix
+    pub name: &'a str,
+    /// animation parameters, defaulting to those of Endless Sky
+    pub animation: SpriteAnimation,
+}
+
+/// How the frames of a sprite are played
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SpriteAnimation {
+    /// frames shown per second, from either `frame rate` or `frame time`
+    pub frame_rate: f32,
+    /// number of frames to wait on the first frame before playing the animation again
+    pub delay: u32,
+    /// frame at which the animation starts
+    pub start_frame: u32,
+    /// whether each instance of the sprite should start at a random frame
+    pub random_start_frame: bool,
+    /// whether the animation stops on its last frame instead of looping
+    pub no_repeat: bool,
+    /// whether the animation plays backwards once it reaches its last frame
+    pub rewind: bool,
+    /// scale of the sprite
+    pub scale: f32,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> SpriteAnimation {
+        SpriteAnimation {
+            frame_rate: 2.,
+            delay: 0,
+            start_frame: 0,
+            random_start_frame: false,
+            no_repeat: false,
+            rewind: false,
+            scale: 1.,
+        }
+    }
 }
 
 /// A ship
@@ -343,6 +489,384 @@ pub struct Ship<'a> {
     pub description: Vec<&'a str>,
 }
 
+/// A list of ships sold together in shipyards
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Shipyard<'a> {
+    /// name of the list
+    pub name: &'a str,
+    /// ships in this list
+    pub ships: Vec<&'a str>,
+}
+
+/// A list of outfits sold together in outfitters
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Outfitter<'a> {
+    /// name of the list
+    pub name: &'a str,
+    /// outfits in this list
+    pub outfits: Vec<&'a str>,
+}
+
+/// What can be bought on a planet
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sales<'a> {
+    /// ships sold in the shipyard
+    pub ships: Vec<&'a Ship<'a>>,
+    /// outfits sold in the outfitter
+    pub outfits: Vec<&'a str>,
+}
+
+/// A node of data kept as is, with the tokens of its line and the nodes indented below it
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Node<'a> {
+    /// tokens of the line
+    pub tokens: Vec<&'a str>,
+    /// nodes indented below this one
+    #[builder(default)]
+    pub children: Vec<Node<'a>>,
+}
+
+/// A commodity that can be traded
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Commodity<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// lowest price, absent for special commodities
+    #[builder(default)]
+    pub low: Option<u32>,
+    /// highest price, absent for special commodities
+    #[builder(default)]
+    pub high: Option<u32>,
+    /// names of the goods of this commodity
+    #[builder(default)]
+    pub items: Vec<&'a str>,
+}
+
+/// Commodities that can be traded
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Commodities<'a> {
+    /// list of commodities
+    pub commodities: Vec<Commodity<'a>>,
+}
+
+/// A part of a phrase, choosing randomly between weighted items
+#[derive(Debug, PartialEq, Clone)]
+pub enum PhrasePart<'a> {
+    /// choice between words
+    Word(Vec<(&'a str, u32)>),
+    /// choice between other phrases, by name
+    Phrase(Vec<(&'a str, u32)>),
+}
+
+/// A random text generator
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Phrase<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// parts of the phrase, each generated then concatenated
+    pub parts: Vec<PhrasePart<'a>>,
+}
+
+/// A news item that can be shown in a spaceport
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct News<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// filter on where this news can be shown, kept as is
+    #[builder(default)]
+    pub location: Vec<Node<'a>>,
+    /// name of the person giving the news
+    #[builder(default)]
+    pub speaker: Vec<PhrasePart<'a>>,
+    /// portraits that can be shown for the person
+    #[builder(default)]
+    pub portrait: Vec<&'a str>,
+    /// the news
+    #[builder(default)]
+    pub message: Vec<PhrasePart<'a>>,
+}
+
+/// A visual effect, like an explosion
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Effect<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// it's sprite
+    #[builder(default)]
+    pub sprite: Option<Sprite<'a>>,
+    /// sound played when the effect is created
+    #[builder(default)]
+    pub sound: Option<&'a str>,
+    /// how long the effect lasts
+    #[builder(default)]
+    pub lifetime: Option<u32>,
+    /// random variation added to the lifetime
+    #[builder(default)]
+    pub random_lifetime: Option<u32>,
+    /// how much of the velocity of the source is kept
+    #[builder(default)]
+    pub velocity_scale: Option<f32>,
+    /// random variation of the angle
+    #[builder(default)]
+    pub random_angle: Option<f32>,
+    /// random spin
+    #[builder(default)]
+    pub random_spin: Option<f32>,
+    /// random velocity
+    #[builder(default)]
+    pub random_velocity: Option<f32>,
+    /// random variation of the frame rate
+    #[builder(default)]
+    pub random_frame_rate: Option<f32>,
+}
+
+/// An environmental hazard, like an ion storm
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Hazard<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// weapon attributes of the hazard, kept as is
+    #[builder(default)]
+    pub weapon: Vec<Node<'a>>,
+    /// if the strength stays the same during the hazard
+    #[builder(default)]
+    pub constant_strength: bool,
+    /// period (?)
+    #[builder(default)]
+    pub period: Option<u32>,
+    /// minimum and maximum duration
+    #[builder(default)]
+    pub duration: Option<(u32, Option<u32>)>,
+    /// minimum and maximum strength
+    #[builder(default)]
+    pub strength: Option<(f32, Option<f32>)>,
+    /// effects shown while the hazard is active, with their count
+    #[builder(default)]
+    pub environmental_effect: Vec<(&'a str, u32)>,
+}
+
+/// A named color
+#[derive(Debug, PartialEq, Clone, Copy, Builder)]
+#[builder(setter(into))]
+pub struct Color<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// red component, between 0 and 1
+    pub red: f32,
+    /// green component, between 0 and 1
+    pub green: f32,
+    /// blue component, between 0 and 1
+    pub blue: f32,
+    /// alpha component, between 0 and 1
+    pub alpha: f32,
+}
+
+/// An element of an interface
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct InterfaceElement<'a> {
+    /// kind of element: `sprite`, `label`, `button`, `box`, ...
+    pub kind: &'a str,
+    /// arguments of the element, like the sprite or the text to show
+    #[builder(default)]
+    pub arguments: Vec<&'a str>,
+    /// condition to show this element
+    #[builder(default)]
+    pub visible_if: Option<&'a str>,
+    /// condition for this element to be active
+    #[builder(default)]
+    pub active_if: Option<&'a str>,
+    /// center of the element
+    #[builder(default)]
+    pub center: Option<Position>,
+    /// dimensions of the element
+    #[builder(default)]
+    pub dimensions: Option<Position>,
+    /// top left corner of the element
+    #[builder(default)]
+    pub from: Option<Position>,
+    /// bottom right corner of the element
+    #[builder(default)]
+    pub to: Option<Position>,
+    /// alignment of the element
+    #[builder(default)]
+    pub align: Vec<&'a str>,
+    /// size of the element, like the font size or the bar width
+    #[builder(default)]
+    pub size: Option<f32>,
+    /// name of the color of the element
+    #[builder(default)]
+    pub color: Option<&'a str>,
+    /// other properties, kept as is
+    #[builder(default)]
+    pub others: Vec<Node<'a>>,
+}
+
+/// An interface, describing a part of the UI
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Interface<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// where the interface is anchored on screen
+    #[builder(default)]
+    pub anchor: Vec<&'a str>,
+    /// named values
+    #[builder(default)]
+    pub values: Vec<(&'a str, f32)>,
+    /// elements of the interface
+    #[builder(default)]
+    pub elements: Vec<InterfaceElement<'a>>,
+}
+
+/// A text substitution
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Substitution<'a> {
+    /// text to replace
+    pub key: &'a str,
+    /// replacement
+    pub value: &'a str,
+    /// conditions for this substitution, kept as is
+    #[builder(default)]
+    pub conditions: Vec<Node<'a>>,
+}
+
+/// A wormhole, linking distant systems
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Wormhole<'a> {
+    /// it's name, that of the planet it is
+    pub name: &'a str,
+    /// if the wormhole links are shown on the map
+    #[builder(default)]
+    pub mappable: bool,
+    /// links between systems, from the first one to the second one
+    #[builder(default)]
+    pub links: Vec<(&'a str, &'a str)>,
+    /// name of the color of the links on the map
+    #[builder(default)]
+    pub color: Option<&'a str>,
+}
+
+/// A link between two systems through a wormhole
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WormholeLink<'a> {
+    /// name of the wormhole
+    pub wormhole: &'a str,
+    /// system from which the wormhole can be entered
+    pub from: &'a str,
+    /// system to which the wormhole leads
+    pub to: &'a str,
+    /// if the link is shown on the map
+    pub mappable: bool,
+}
+
+/// A ship owned by the player, as saved
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct SavedShip<'a> {
+    /// model of the ship
+    pub model: &'a str,
+    /// name given to the ship
+    #[builder(default)]
+    pub name: Option<&'a str>,
+    /// unique identifier of the ship
+    #[builder(default)]
+    pub uuid: Option<&'a str>,
+    /// outfits installed on the ship, with their count
+    #[builder(default)]
+    pub outfits: Vec<(&'a str, u32)>,
+    /// crew on board
+    #[builder(default)]
+    pub crew: Option<u32>,
+    /// fuel remaining
+    #[builder(default)]
+    pub fuel: Option<f32>,
+    /// shields remaining
+    #[builder(default)]
+    pub shields: Option<f32>,
+    /// hull remaining
+    #[builder(default)]
+    pub hull: Option<f32>,
+    /// system where the ship is
+    #[builder(default)]
+    pub system: Option<&'a str>,
+    /// planet where the ship is
+    #[builder(default)]
+    pub planet: Option<&'a str>,
+    /// if the ship is parked
+    #[builder(default)]
+    pub parked: bool,
+    /// other fields, kept as is
+    #[builder(default)]
+    pub others: Vec<Node<'a>>,
+}
+
+/// Cargo of the player's fleet
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Cargo<'a> {
+    /// commodities carried, with their tonnage
+    pub commodities: Vec<(&'a str, u32)>,
+    /// outfits carried, with their count
+    pub outfits: Vec<(&'a str, u32)>,
+}
+
+/// A saved game of a player
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct SaveGame<'a> {
+    /// first name of the pilot
+    pub first_name: &'a str,
+    /// last name of the pilot
+    pub last_name: &'a str,
+    /// current date
+    pub date: Date,
+    /// current system
+    pub system: &'a str,
+    /// current planet, if landed
+    #[builder(default)]
+    pub planet: Option<&'a str>,
+    /// account of the player
+    pub account: Account,
+    /// debts other than the mortgage, like fines, with their kind
+    #[builder(default)]
+    pub debts: Vec<(&'a str, Mortgage)>,
+    /// ships owned by the player, the first one being the flagship
+    #[builder(default)]
+    pub ships: Vec<SavedShip<'a>>,
+    /// cargo of the fleet
+    #[builder(default)]
+    pub cargo: Cargo<'a>,
+    /// systems visited
+    #[builder(default)]
+    pub visited: Vec<&'a str>,
+    /// planets visited
+    #[builder(default)]
+    pub visited_planets: Vec<&'a str>,
+    /// conditions set, with their value
+    #[builder(default)]
+    pub conditions: Vec<(&'a str, i64)>,
+    /// reputation with governments
+    #[builder(default)]
+    pub reputation: Vec<(&'a str, f32)>,
+    /// missions in progress, kept as is
+    #[builder(default)]
+    pub missions: Vec<Node<'a>>,
+    /// other fields, kept as is
+    #[builder(default)]
+    pub others: Vec<Node<'a>>,
+}
+
 /// list of top level objects that can be parsed
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
@@ -357,4 +881,26 @@ pub enum Object<'a> {
     System(System<'a>),
     /// a ship
     Ship(Ship<'a>),
+    /// a list of ships for sale
+    Shipyard(Shipyard<'a>),
+    /// a list of outfits for sale
+    Outfitter(Outfitter<'a>),
+    /// commodities that can be traded
+    Trade(Commodities<'a>),
+    /// a random text generator
+    Phrase(Phrase<'a>),
+    /// a news item
+    News(News<'a>),
+    /// a visual effect
+    Effect(Effect<'a>),
+    /// an environmental hazard
+    Hazard(Hazard<'a>),
+    /// a named color
+    Color(Color<'a>),
+    /// an interface
+    Interface(Interface<'a>),
+    /// text substitutions
+    Substitutions(Vec<Substitution<'a>>),
+    /// a wormhole
+    Wormhole(Wormhole<'a>),
 }
@@ -0,0 +1,212 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space1,
+    error::context,
+    sequence::{separated_pair, tuple},
+    IResult,
+};
+
+use crate::helpers::{eols, indent, string};
+use crate::types::{Object, SystemObject, Wormhole, WormholeLink};
+use crate::DataError;
+
+pub fn parse_wormhole<'a>(input: &'a str) -> IResult<&'a str, Wormhole<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) = context(
+        "wormhole tag",
+        tuple((tag("wormhole"), space1, string, eols)),
+    )(input)?;
+
+    let mut builder = crate::types::WormholeBuilder::default();
+    builder.name(name);
+    let mut links = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, mappable, |input| Ok((input, true)), input, builder);
+        crate::parse_item_in_loop!(1, color, string, input, builder);
+        crate::push_item_in_loop!(
+            1,
+            "link",
+            separated_pair(string, space1, string),
+            input,
+            links
+        );
+
+        break;
+    }
+    builder.links(links);
+
+    builder
+        .build()
+        .map(|wormhole| (input, wormhole))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("wormhole"),
+            })
+        })
+}
+
+/// List all the links between systems through wormholes. Links are taken from the `wormhole`
+/// definitions, or for planets flagged as wormholes without one, each system the planet is in
+/// leads to the next one, and the last one back to the first.
+pub fn wormhole_links<'a>(objects: &[Object<'a>]) -> Vec<WormholeLink<'a>> {
+    let wormholes = objects
+        .iter()
+        .filter_map(|object| match object {
+            Object::Wormhole(wormhole) => Some(wormhole),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut links = wormholes
+        .iter()
+        .flat_map(|wormhole| {
+            wormhole.links.iter().map(move |(from, to)| WormholeLink {
+                wormhole: wormhole.name,
+                from,
+                to,
+                mappable: wormhole.mappable,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for object in objects {
+        if let Object::Planet(planet) = object {
+            if !planet.wormhole
+                || wormholes
+                    .iter()
+                    .any(|wormhole| wormhole.name == planet.name)
+            {
+                continue;
+            }
+            let systems = objects
+                .iter()
+                .filter_map(|object| match object {
+                    Object::System(system) if contains(&system.objects, planet.name) => {
+                        Some(system.name)
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if systems.len() < 2 {
+                continue;
+            }
+            links.extend(systems.iter().enumerate().map(|(i, from)| WormholeLink {
+                wormhole: planet.name,
+                from,
+                to: systems[(i + 1) % systems.len()],
+                mappable: false,
+            }));
+        }
+    }
+
+    links
+}
+
+fn contains(objects: &[SystemObject], name: &str) -> bool {
+    objects
+        .iter()
+        .any(|object| object.name == Some(name) || contains(&object.objects, name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_parse_wormhole() {
+        let data = r#"wormhole "The Eye"
+	mappable
+	link "Ultima Thule" "Ildaria"
+	link "Ildaria" "Ultima Thule"
+	color "map wormhole"
+"#;
+
+        let parsed = dbg!(parse_wormhole(data));
+        assert!(parsed.is_ok());
+        let wormhole = parsed.unwrap().1;
+
+        assert_eq!(wormhole.name, "The Eye");
+        assert!(wormhole.mappable);
+        assert_eq!(
+            wormhole.links,
+            vec![("Ultima Thule", "Ildaria"), ("Ildaria", "Ultima Thule")]
+        );
+        assert_eq!(wormhole.color, Some("map wormhole"));
+    }
+
+    #[test]
+    fn can_list_wormhole_links() {
+        let data = r#"planet "The Eye"
+	description `A wormhole.`
+	wormhole
+
+planet "Pug Wormhole"
+	description `Another one.`
+	wormhole
+
+wormhole "The Eye"
+	link "First" "Second"
+
+system First
+	pos 0 0
+	government Uninhabited
+	habitable 100
+	link Second
+	object "The Eye"
+		period 10
+	object "Pug Wormhole"
+		period 10
+
+system Second
+	pos 0 100
+	government Uninhabited
+	habitable 100
+	object "The Eye"
+		period 10
+
+system Third
+	pos 100 100
+	government Uninhabited
+	habitable 100
+	object
+		period 10
+		object "Pug Wormhole"
+			period 10
+"#;
+
+        let objects = crate::parse(data);
+        assert_eq!(
+            wormhole_links(&objects),
+            vec![
+                WormholeLink {
+                    wormhole: "The Eye",
+                    from: "First",
+                    to: "Second",
+                    mappable: false,
+                },
+                WormholeLink {
+                    wormhole: "Pug Wormhole",
+                    from: "First",
+                    to: "Third",
+                    mappable: false,
+                },
+                WormholeLink {
+                    wormhole: "Pug Wormhole",
+                    from: "Third",
+                    to: "First",
+                    mappable: false,
+                },
+            ]
+        );
+        let first = objects
+            .iter()
+            .find_map(|object| match object {
+                Object::System(system) if system.name == "First" => Some(system),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(first.links, vec!["Second"]);
+    }
+}
@@ -0,0 +1,930 @@
+use std::fmt;
+
+use crate::types::{
+    ArrivalDistance, Color, Galaxy, Node, Planet, Ship, Sprite, SpriteAnimation, Start, System,
+    SystemObject, Wormhole,
+};
+
+/// A token written so that it is read back as is, between quotes or backticks
+#[derive(Debug, Clone, Copy)]
+pub struct Token<'a>(&'a str);
+
+impl<'a> Token<'a> {
+    /// A token for a value, if it can be written: it can't contain both quotes and backticks, as
+    /// there would be no way to delimit it, nor line breaks
+    pub fn new(value: &'a str) -> Option<Token<'a>> {
+        let delimited = !(value.contains('"') && value.contains('`'));
+        if delimited && !value.contains(['\n', '\r']) {
+            Some(Token(value))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> fmt::Display for Token<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.contains('"') {
+            write!(f, "`{}`", self.0)
+        } else {
+            write!(f, "\"{}\"", self.0)
+        }
+    }
+}
+
+impl<'a> fmt::Display for Color<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "color {} {} {} {} {}",
+            Token(self.name),
+            self.red,
+            self.green,
+            self.blue,
+            self.alpha
+        )
+    }
+}
+
+impl<'a> fmt::Display for Galaxy<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "galaxy {}", Token(self.name))?;
+        writeln!(f, "\tpos {} {}", self.pos.x, self.pos.y)?;
+        if let Some(sprite) = self.sprite {
+            writeln!(f, "\tsprite {}", Token(sprite))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Wormhole<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "wormhole {}", Token(self.name))?;
+        if self.mappable {
+            writeln!(f, "\tmappable")?;
+        }
+        for (from, to) in &self.links {
+            writeln!(f, "\tlink {} {}", Token(from), Token(to))?;
+        }
+        if let Some(color) = self.color {
+            writeln!(f, "\tcolor {}", Token(color))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Start<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(name) => writeln!(f, "start {}", Token(name))?,
+            None => writeln!(f, "start")?,
+        }
+        let date = self.date;
+        writeln!(f, "\tdate {} {} {}", date.day, date.month, date.year)?;
+        writeln!(f, "\tsystem {}", Token(self.system))?;
+        writeln!(f, "\tplanet {}", Token(self.planet))?;
+        writeln!(f, "\taccount")?;
+        writeln!(f, "\t\tcredits {}", self.account.credits)?;
+        writeln!(f, "\t\tscore {}", self.account.score)?;
+        if let Some(mortgage) = self.account.mortgage {
+            writeln!(f, "\t\tmortgage Mortgage")?;
+            writeln!(f, "\t\t\tprincipal {}", mortgage.principal)?;
+            writeln!(f, "\t\t\tinterest {}", mortgage.interest)?;
+            writeln!(f, "\t\t\tterm {}", mortgage.term)?;
+        }
+        writeln!(f, "\tset {}", Token(self.set))?;
+        for ship in &self.ships {
+            write!(f, "\tship {}", Token(ship.model))?;
+            if let Some(name) = ship.name {
+                write!(f, " {}", Token(name))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Planet<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "planet {}", Token(self.name))?;
+        if !self.attributes.is_empty() {
+            writeln!(f, "\tattributes {}", Tokens(&self.attributes))?;
+        }
+        if let Some(landscape) = self.landscape {
+            writeln!(f, "\tlandscape {}", Token(landscape))?;
+        }
+        if let Some(government) = self.government {
+            writeln!(f, "\tgovernment {}", Token(government))?;
+        }
+        if let Some(music) = self.music {
+            writeln!(f, "\tmusic {}", Token(music))?;
+        }
+        for line in &self.description {
+            writeln!(f, "\tdescription {}", Token(line))?;
+        }
+        for line in &self.spaceport {
+            writeln!(f, "\tspaceport {}", Token(line))?;
+        }
+        for shipyard in &self.shipyard {
+            writeln!(f, "\tshipyard {}", Token(shipyard))?;
+        }
+        for outfitter in &self.outfitter {
+            writeln!(f, "\toutfitter {}", Token(outfitter))?;
+        }
+        if let Some(bribe) = self.bribe {
+            writeln!(f, "\tbribe {}", bribe)?;
+        }
+        if let Some(security) = self.security {
+            writeln!(f, "\tsecurity {}", security)?;
+        }
+        if let Some(reputation) = self.required_reputation {
+            writeln!(f, "\t\"required reputation\" {}", reputation)?;
+        }
+        if let Some(tribute) = &self.tribute {
+            writeln!(f, "\ttribute {}", tribute.value)?;
+            writeln!(f, "\t\tthreshold {}", tribute.threshold)?;
+            let fleet = &tribute.fleet;
+            writeln!(f, "\t\tfleet {} {}", Token(fleet.kind), fleet.count)?;
+        }
+        if self.wormhole {
+            writeln!(f, "\twormhole")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for System<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "system {}", Token(self.name))?;
+        writeln!(f, "\tpos {} {}", self.pos.x, self.pos.y)?;
+        writeln!(f, "\tgovernment {}", Token(self.government))?;
+        if !self.attributes.is_empty() {
+            writeln!(f, "\tattributes {}", Tokens(&self.attributes))?;
+        }
+        if let Some(music) = self.music {
+            writeln!(f, "\tmusic {}", Token(music))?;
+        }
+        if let Some(arrival) = self.arrival {
+            write_arrival_distance(f, "arrival", arrival)?;
+        }
+        if let Some(departure) = self.departure {
+            write_arrival_distance(f, "departure", departure)?;
+        }
+        writeln!(f, "\thabitable {}", self.habitable)?;
+        if let Some(belt) = self.belt {
+            writeln!(f, "\tbelt {}", belt)?;
+        }
+        if let Some(haze) = self.haze {
+            writeln!(f, "\thaze {}", Token(haze))?;
+        }
+        if let Some(ramscoop) = self.ramscoop {
+            writeln!(f, "\tramscoop")?;
+            if let Some(universal) = ramscoop.universal {
+                writeln!(f, "\t\tuniversal {}", u8::from(universal))?;
+            }
+            if let Some(addend) = ramscoop.addend {
+                writeln!(f, "\t\taddend {}", addend)?;
+            }
+            if let Some(multiplier) = ramscoop.multiplier {
+                writeln!(f, "\t\tmultiplier {}", multiplier)?;
+            }
+        }
+        if let Some(fence) = self.invisible_fence {
+            writeln!(f, "\t\"invisible fence\" {}", fence)?;
+        }
+        if let Some(range) = self.jump_range {
+            writeln!(f, "\t\"jump range\" {}", range)?;
+        }
+        if let Some(density) = self.starfield_density {
+            writeln!(f, "\t\"starfield density\" {}", density)?;
+        }
+        for link in &self.links {
+            writeln!(f, "\tlink {}", Token(link))?;
+        }
+        for asteroids in &self.asteroids {
+            writeln!(
+                f,
+                "\tasteroids {} {} {}",
+                Token(asteroids.name),
+                asteroids.first_value,
+                asteroids.second_value
+            )?;
+        }
+        for minables in &self.minables {
+            writeln!(
+                f,
+                "\tminables {} {} {}",
+                Token(minables.name),
+                minables.first_value,
+                minables.second_value
+            )?;
+        }
+        for trade in &self.trades {
+            writeln!(f, "\ttrade {} {}", Token(trade.name), trade.price)?;
+        }
+        for fleet in &self.fleets {
+            writeln!(f, "\tfleet {} {}", Token(fleet.kind), fleet.count)?;
+        }
+        for raid in &self.raids {
+            write!(f, "\traid {}", Token(raid.fleet))?;
+            // the maximum attraction can only be given after the minimum one
+            if let Some(minimum) = raid.minimum_attraction {
+                write!(f, " {}", minimum)?;
+                if let Some(maximum) = raid.maximum_attraction {
+                    write!(f, " {}", maximum)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        for hazard in &self.hazards {
+            writeln!(f, "\thazard {} {}", Token(hazard.name), hazard.period)?;
+        }
+        for object in &self.objects {
+            write_object(f, object, 0)?;
+        }
+        for node in &self.others {
+            write_node(f, node, 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Ship<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ship {}", Token(self.name))?;
+        if let Some(subclass) = self.subclass {
+            write!(f, " {}", Token(subclass))?;
+        }
+        writeln!(f)?;
+        if let Some(plural) = self.plural {
+            writeln!(f, "\tplural {}", Token(plural))?;
+        }
+        write_sprite(f, &self.sprite, 1)?;
+        writeln!(f, "\tthumbnail {}", Token(self.thumbnail))?;
+
+        let attributes = &self.attributes;
+        writeln!(f, "\tattributes")?;
+        if !attributes.licenses.is_empty() {
+            writeln!(f, "\t\tlicenses")?;
+            for license in &attributes.licenses {
+                writeln!(f, "\t\t\t{}", Token(license))?;
+            }
+        }
+        writeln!(f, "\t\tcategory {}", Token(attributes.category))?;
+        writeln!(f, "\t\t\"cost\" {}", attributes.cost)?;
+        writeln!(f, "\t\t\"shields\" {}", attributes.shields)?;
+        writeln!(f, "\t\t\"hull\" {}", attributes.hull)?;
+        if attributes.automaton {
+            writeln!(f, "\t\t\"automaton\" 1")?;
+        }
+        writeln!(f, "\t\t\"required crew\" {}", attributes.required_crew)?;
+        writeln!(f, "\t\t\"bunks\" {}", attributes.bunks)?;
+        writeln!(f, "\t\t\"mass\" {}", attributes.mass)?;
+        writeln!(f, "\t\t\"drag\" {}", attributes.drag)?;
+        writeln!(
+            f,
+            "\t\t\"heat dissipation\" {}",
+            attributes.heat_dissipation
+        )?;
+        writeln!(f, "\t\t\"fuel capacity\" {}", attributes.fuel_capacity)?;
+        writeln!(f, "\t\t\"cargo space\" {}", attributes.cargo_space)?;
+        writeln!(f, "\t\t\"outfit space\" {}", attributes.outfit_space)?;
+        writeln!(f, "\t\t\"weapon capacity\" {}", attributes.weapon_capacity)?;
+        writeln!(f, "\t\t\"engine capacity\" {}", attributes.engine_capacity)?;
+        let weapon = attributes.weapon;
+        writeln!(f, "\t\tweapon")?;
+        writeln!(f, "\t\t\t\"blast radius\" {}", weapon.blast_radius)?;
+        writeln!(f, "\t\t\t\"shield damage\" {}", weapon.shield_damage)?;
+        writeln!(f, "\t\t\t\"hull damage\" {}", weapon.hull_damage)?;
+        writeln!(f, "\t\t\t\"hit force\" {}", weapon.hit_force)?;
+
+        writeln!(f, "\toutfits")?;
+        for (outfit, count) in &self.outfits {
+            writeln!(f, "\t\t{} {}", Token(outfit), count)?;
+        }
+        for (x, y, zoom) in &self.engine {
+            write!(f, "\tengine {} {}", x, y)?;
+            if let Some(zoom) = zoom {
+                write!(f, " {}", zoom)?;
+            }
+            writeln!(f)?;
+        }
+        for (kind, hardpoints) in &[
+            ("gun", &self.gun),
+            ("turret", &self.turret),
+            ("fighter", &self.fighter),
+            ("drone", &self.drone),
+        ] {
+            for (x, y, content) in hardpoints.iter() {
+                write!(f, "\t{} {} {}", kind, x, y)?;
+                if let Some(content) = content {
+                    write!(f, " {}", Token(content))?;
+                }
+                writeln!(f)?;
+            }
+        }
+        for (leak, open, close) in &self.leak {
+            writeln!(f, "\tleak {} {} {}", Token(leak), open, close)?;
+        }
+        for (effect, count) in &self.explode {
+            writeln!(f, "\texplode {} {}", Token(effect), count)?;
+        }
+        if let Some(effect) = self.final_explode {
+            writeln!(f, "\t\"final explode\" {}", Token(effect))?;
+        }
+        for line in &self.description {
+            writeln!(f, "\tdescription {}", Token(line))?;
+        }
+        Ok(())
+    }
+}
+
+/// Tokens written on a line, separated by spaces
+struct Tokens<'a, 'b>(&'b [&'a str]);
+
+impl<'a, 'b> fmt::Display for Tokens<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, token) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", Token(token))?;
+        }
+        Ok(())
+    }
+}
+
+fn indent(f: &mut fmt::Formatter, level: usize) -> fmt::Result {
+    (0..level).try_for_each(|_| write!(f, "\t"))
+}
+
+/// Write where ships arrive or depart, on a single line when it is the same for links and jumps
+fn write_arrival_distance(
+    f: &mut fmt::Formatter,
+    key: &str,
+    distance: ArrivalDistance,
+) -> fmt::Result {
+    match (distance.link, distance.jump) {
+        (Some(link), Some(jump)) if link == jump => writeln!(f, "\t{} {}", key, link),
+        (link, jump) => {
+            writeln!(f, "\t{}", key)?;
+            if let Some(link) = link {
+                writeln!(f, "\t\tlink {}", link)?;
+            }
+            if let Some(jump) = jump {
+                writeln!(f, "\t\tjump {}", jump)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Write a sprite at an indentation level, with the animation parameters that aren't the
+/// default ones below it
+fn write_sprite(f: &mut fmt::Formatter, sprite: &Sprite, level: usize) -> fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "sprite {}", Token(sprite.name))?;
+    let animation = sprite.animation;
+    let default = SpriteAnimation::default();
+    if animation.frame_rate != default.frame_rate {
+        indent(f, level + 1)?;
+        writeln!(f, "\"frame rate\" {}", animation.frame_rate)?;
+    }
+    if animation.delay != default.delay {
+        indent(f, level + 1)?;
+        writeln!(f, "delay {}", animation.delay)?;
+    }
+    if animation.start_frame != default.start_frame {
+        indent(f, level + 1)?;
+        writeln!(f, "\"start frame\" {}", animation.start_frame)?;
+    }
+    if animation.scale != default.scale {
+        indent(f, level + 1)?;
+        writeln!(f, "scale {}", animation.scale)?;
+    }
+    for (flag, set) in &[
+        ("\"random start frame\"", animation.random_start_frame),
+        ("\"no repeat\"", animation.no_repeat),
+        ("rewind", animation.rewind),
+    ] {
+        if *set {
+            indent(f, level + 1)?;
+            writeln!(f, "{}", flag)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write an object of a system and its moons, `level` being the number of objects it is in
+fn write_object(f: &mut fmt::Formatter, object: &SystemObject, level: usize) -> fmt::Result {
+    indent(f, level + 1)?;
+    match object.name {
+        Some(name) => writeln!(f, "object {}", Token(name))?,
+        None => writeln!(f, "object")?,
+    }
+    if let Some(sprite) = &object.sprite {
+        write_sprite(f, sprite, level + 2)?;
+    }
+    if let Some(distance) = object.distance {
+        indent(f, level + 2)?;
+        writeln!(f, "distance {}", distance)?;
+    }
+    indent(f, level + 2)?;
+    writeln!(f, "period {}", object.period)?;
+    if let Some(offset) = object.offset {
+        indent(f, level + 2)?;
+        writeln!(f, "offset {}", offset)?;
+    }
+    for hazard in &object.hazards {
+        indent(f, level + 2)?;
+        writeln!(f, "hazard {} {}", Token(hazard.name), hazard.period)?;
+    }
+    for moon in &object.objects {
+        write_object(f, moon, level + 1)?;
+    }
+    for node in &object.others {
+        write_node(f, node, level + 2)?;
+    }
+    Ok(())
+}
+
+/// Write a node kept as is and the nodes below it, at an indentation level
+fn write_node(f: &mut fmt::Formatter, node: &Node, level: usize) -> fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "{}", Tokens(&node.tokens))?;
+    for child in &node.children {
+        write_node(f, child, level + 1)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::Token;
+    use crate::types::*;
+    use crate::validate;
+
+    /// any name that can be written, with spaces, quotes, backticks or any other character
+    fn name() -> impl Strategy<Value = String> {
+        prop_oneof!["[a-zA-Z0-9 '\"`-]{0,16}", "[^\r\n]{0,16}"]
+            .prop_filter("unwritable name", |name| Token::new(name).is_some())
+    }
+
+    /// a name living as long as the test, to build data borrowing it from a strategy
+    fn static_name() -> impl Strategy<Value = &'static str> {
+        name().prop_map(|name| &*Box::leak(name.into_boxed_str()))
+    }
+
+    fn names(max: usize) -> impl Strategy<Value = Vec<&'static str>> {
+        prop::collection::vec(static_name(), 0..max)
+    }
+
+    fn number() -> impl Strategy<Value = f32> {
+        use prop::num::f32::*;
+        POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO
+    }
+
+    fn position() -> impl Strategy<Value = Position> {
+        use prop::num::f64::*;
+        let coordinate = || POSITIVE | NEGATIVE | NORMAL | SUBNORMAL | ZERO;
+        (coordinate(), coordinate()).prop_map(|(x, y)| Position { x, y })
+    }
+
+    /// the only object read back from written data
+    fn read(written: &str) -> Result<Object<'_>, String> {
+        match validate(written) {
+            Ok((_, mut objects)) if objects.len() == 1 => Ok(objects.remove(0)),
+            Ok((_, objects)) => Err(format!("unexpected objects {:?}", objects)),
+            Err(error) => Err(format!("{:?}", error)),
+        }
+    }
+
+    prop_compose! {
+        fn sprite()(
+            name in static_name(),
+            frame_rate in number(),
+            (delay, start_frame) in any::<(u32, u32)>(),
+            scale in number(),
+            (random_start_frame, no_repeat, rewind) in any::<(bool, bool, bool)>(),
+        ) -> Sprite<'static> {
+            Sprite {
+                name,
+                animation: SpriteAnimation {
+                    frame_rate,
+                    delay,
+                    start_frame,
+                    random_start_frame,
+                    no_repeat,
+                    rewind,
+                    scale,
+                },
+            }
+        }
+    }
+
+    prop_compose! {
+        fn start()(
+            name in prop::option::of(static_name()),
+            (day, month, year) in any::<(u8, u8, u16)>(),
+            (system, planet, set) in (static_name(), static_name(), static_name()),
+            (credits, score) in any::<(u64, u32)>(),
+            mortgage in prop::option::of((any::<u64>(), number(), any::<u16>())),
+            ships in prop::collection::vec((static_name(), prop::option::of(static_name())), 0..3),
+        ) -> Start<'static> {
+            Start {
+                name,
+                date: Date { year, month, day },
+                system,
+                planet,
+                account: Account {
+                    credits,
+                    score,
+                    mortgage: mortgage.map(|(principal, interest, term)| Mortgage {
+                        principal,
+                        interest,
+                        term,
+                    }),
+                },
+                set,
+                ships: ships
+                    .into_iter()
+                    .map(|(model, name)| StartShip { model, name })
+                    .collect(),
+            }
+        }
+    }
+
+    prop_compose! {
+        fn planet()(
+            name in static_name(),
+            attributes in names(4),
+            (landscape, government, music) in prop::option::of(static_name()).prop_flat_map(
+                |landscape| (Just(landscape), prop::option::of(static_name()), prop::option::of(static_name()))
+            ),
+            description in prop::collection::vec(static_name(), 1..4),
+            (spaceport, shipyard, outfitter) in (names(3), names(3), names(3)),
+            (bribe, security, required_reputation) in (
+                prop::option::of(number()),
+                prop::option::of(number()),
+                prop::option::of(number()),
+            ),
+            tribute in prop::option::of((any::<(u32, u32)>(), static_name(), any::<u16>())),
+            wormhole in any::<bool>(),
+        ) -> Planet<'static> {
+            Planet {
+                name,
+                attributes,
+                landscape,
+                government,
+                music,
+                description,
+                spaceport,
+                shipyard,
+                outfitter,
+                bribe,
+                security,
+                tribute: tribute.map(|((value, threshold), kind, count)| Tribute {
+                    value,
+                    threshold,
+                    fleet: Fleet { kind, count },
+                }),
+                required_reputation,
+                wormhole,
+            }
+        }
+    }
+
+    /// nodes kept as is, their first token being unknown to the parsers
+    fn others(max: usize) -> impl Strategy<Value = Vec<Node<'static>>> {
+        let key = "custom [a-z]{0,8}".prop_map(|key| &*Box::leak(key.into_boxed_str()));
+        let child = names(3)
+            .prop_filter("empty line", |tokens| !tokens.is_empty())
+            .prop_map(|tokens| Node {
+                tokens,
+                children: vec![],
+            });
+        let node = (key, names(3), prop::collection::vec(child, 0..3)).prop_map(
+            |(key, mut tokens, children)| {
+                tokens.insert(0, key);
+                Node { tokens, children }
+            },
+        );
+        prop::collection::vec(node, 0..max)
+    }
+
+    fn hazards() -> impl Strategy<Value = Vec<SystemHazard<'static>>> {
+        prop::collection::vec(
+            (static_name(), any::<u32>()).prop_map(|(name, period)| SystemHazard { name, period }),
+            0..3,
+        )
+    }
+
+    prop_compose! {
+        fn object(moons: BoxedStrategy<Vec<SystemObject<'static>>>)(
+            name in prop::option::of(static_name()),
+            sprite in prop::option::of(sprite()),
+            (distance, period, offset) in (prop::option::of(number()), number(), prop::option::of(number())),
+            hazards in hazards(),
+            objects in moons,
+            others in others(2),
+        ) -> SystemObject<'static> {
+            SystemObject {
+                name,
+                sprite,
+                distance,
+                period,
+                offset,
+                hazards,
+                objects,
+                others,
+            }
+        }
+    }
+
+    fn objects() -> impl Strategy<Value = Vec<SystemObject<'static>>> {
+        Just(vec![]).prop_recursive(2, 8, 3, |moons| prop::collection::vec(object(moons), 0..3))
+    }
+
+    fn arrival_distance() -> impl Strategy<Value = ArrivalDistance> {
+        prop_oneof![
+            number().prop_map(|distance| ArrivalDistance {
+                link: Some(distance),
+                jump: Some(distance),
+            }),
+            (prop::option::of(number()), prop::option::of(number()))
+                .prop_map(|(link, jump)| ArrivalDistance { link, jump }),
+        ]
+    }
+
+    prop_compose! {
+        fn system()(
+            (name, government) in (static_name(), static_name()),
+            pos in position(),
+            attributes in names(4),
+            (music, haze) in (prop::option::of(static_name()), prop::option::of(static_name())),
+            (arrival, departure) in (prop::option::of(arrival_distance()), prop::option::of(arrival_distance())),
+            (habitable, belt) in (number(), prop::option::of(any::<u32>())),
+            ramscoop in prop::option::of((
+                prop::option::of(any::<bool>()),
+                prop::option::of(number()),
+                prop::option::of(number()),
+            )),
+            (invisible_fence, jump_range, starfield_density) in (
+                prop::option::of(number()),
+                prop::option::of(number()),
+                prop::option::of(number()),
+            ),
+            links in names(4),
+            (asteroids, minables) in (
+                prop::collection::vec((static_name(), any::<u32>(), number()), 0..3),
+                prop::collection::vec((static_name(), any::<u32>(), number()), 0..3),
+            ),
+            trades in prop::collection::vec((static_name(), any::<u32>()), 0..3),
+            fleets in prop::collection::vec((static_name(), any::<u16>()), 0..3),
+            // the maximum attraction can't be given without the minimum one
+            raids in prop::collection::vec(
+                (static_name(), prop::option::of((number(), prop::option::of(number())))),
+                0..3,
+            ),
+            hazards in hazards(),
+            objects in objects(),
+            others in others(3),
+        ) -> System<'static> {
+            System {
+                name,
+                pos,
+                government,
+                attributes,
+                music,
+                arrival,
+                departure,
+                habitable,
+                belt,
+                haze,
+                ramscoop: ramscoop.map(|(universal, addend, multiplier)| Ramscoop {
+                    universal,
+                    addend,
+                    multiplier,
+                }),
+                invisible_fence,
+                jump_range,
+                starfield_density,
+                links,
+                asteroids: asteroids
+                    .into_iter()
+                    .map(|(name, first_value, second_value)| Asteroids {
+                        name,
+                        first_value,
+                        second_value,
+                    })
+                    .collect(),
+                minables: minables
+                    .into_iter()
+                    .map(|(name, first_value, second_value)| Minables {
+                        name,
+                        first_value,
+                        second_value,
+                    })
+                    .collect(),
+                trades: trades
+                    .into_iter()
+                    .map(|(name, price)| Trade { name, price })
+                    .collect(),
+                fleets: fleets
+                    .into_iter()
+                    .map(|(kind, count)| Fleet { kind, count })
+                    .collect(),
+                raids: raids
+                    .into_iter()
+                    .map(|(fleet, attraction)| Raid {
+                        fleet,
+                        minimum_attraction: attraction.map(|(minimum, _)| minimum),
+                        maximum_attraction: attraction.and_then(|(_, maximum)| maximum),
+                    })
+                    .collect(),
+                hazards,
+                objects,
+                others,
+            }
+        }
+    }
+
+    fn hardpoints() -> impl Strategy<Value = Vec<(f32, f32, Option<&'static str>)>> {
+        prop::collection::vec((number(), number(), prop::option::of(static_name())), 0..3)
+    }
+
+    prop_compose! {
+        fn ship_attributes()(
+            (licenses, category) in (names(3), static_name()),
+            (cost, shields, hull, required_crew, bunks, mass) in any::<(u32, u32, u32, u32, u32, u32)>(),
+            (fuel_capacity, cargo_space, outfit_space, weapon_capacity, engine_capacity)
+                in any::<(u32, u32, u32, u32, u32)>(),
+            automaton in any::<bool>(),
+            (drag, heat_dissipation) in (number(), number()),
+            (blast_radius, shield_damage, hull_damage, hit_force) in any::<(u32, u32, u32, u32)>(),
+        ) -> ShipAttributes<'static> {
+            ShipAttributes {
+                licenses,
+                category,
+                cost,
+                shields,
+                hull,
+                automaton,
+                required_crew,
+                bunks,
+                mass,
+                drag,
+                heat_dissipation,
+                fuel_capacity,
+                cargo_space,
+                outfit_space,
+                weapon_capacity,
+                engine_capacity,
+                weapon: ShipWeapon {
+                    blast_radius,
+                    shield_damage,
+                    hull_damage,
+                    hit_force,
+                },
+            }
+        }
+    }
+
+    prop_compose! {
+        fn ship()(
+            name in static_name(),
+            (subclass, plural, final_explode) in (
+                prop::option::of(static_name()),
+                prop::option::of(static_name()),
+                prop::option::of(static_name()),
+            ),
+            (sprite, thumbnail) in (sprite(), static_name()),
+            attributes in ship_attributes(),
+            outfits in prop::collection::vec((static_name(), any::<u32>()), 1..4),
+            engine in prop::collection::vec(
+                (number(), number(), prop::option::of(number())),
+                1..3,
+            ),
+            (gun, turret, fighter, drone) in (hardpoints(), hardpoints(), hardpoints(), hardpoints()),
+            leak in prop::collection::vec((static_name(), any::<u32>(), any::<u32>()), 0..3),
+            explode in prop::collection::vec((static_name(), any::<u32>()), 1..3),
+            description in prop::collection::vec(static_name(), 1..4),
+        ) -> Ship<'static> {
+            Ship {
+                name,
+                subclass,
+                plural,
+                sprite,
+                thumbnail,
+                attributes,
+                outfits,
+                engine,
+                gun,
+                turret,
+                fighter,
+                drone,
+                leak,
+                explode,
+                final_explode,
+                description,
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn can_read_written_colors(
+            name in name(),
+            components in prop::array::uniform4(number()),
+        ) {
+            let color = Color {
+                name: &name,
+                red: components[0],
+                green: components[1],
+                blue: components[2],
+                alpha: components[3],
+            };
+            let written = color.to_string();
+            let parsed = validate(&written);
+            match parsed {
+                Ok((_, objects)) => match objects.as_slice() {
+                    [Object::Color(parsed)] => prop_assert_eq!(parsed, &color),
+                    _ => prop_assert!(false, "unexpected objects {:?}", objects),
+                },
+                Err(error) => prop_assert!(false, "{:?}", error),
+            }
+        }
+
+        #[test]
+        fn can_read_written_galaxies_and_wormholes(
+            name in name(),
+            pos in position(),
+            sprite in proptest::option::of(name()),
+            mappable in any::<bool>(),
+            links in prop::collection::vec((name(), name()), 0..4),
+            color in proptest::option::of(name()),
+        ) {
+            let galaxy = Galaxy {
+                name: &name,
+                pos,
+                sprite: sprite.as_deref(),
+            };
+            let wormhole = Wormhole {
+                name: &name,
+                mappable,
+                links: links.iter().map(|(from, to)| (from.as_str(), to.as_str())).collect(),
+                color: color.as_deref(),
+            };
+            let written = format!("{}\n{}", galaxy, wormhole);
+            let parsed = validate(&written);
+            match parsed {
+                Ok((_, objects)) => match objects.as_slice() {
+                    [Object::Galaxy(parsed_galaxy), Object::Wormhole(parsed_wormhole)] => {
+                        prop_assert_eq!(parsed_galaxy, &galaxy);
+                        prop_assert_eq!(parsed_wormhole, &wormhole);
+                    }
+                    _ => prop_assert!(false, "unexpected objects {:?}", objects),
+                },
+                Err(error) => prop_assert!(false, "{:?}", error),
+            }
+        }
+
+        #[test]
+        fn can_read_written_starts(start in start()) {
+            let written = start.to_string();
+            match read(&written) {
+                Ok(Object::Start(parsed)) => prop_assert_eq!(parsed, start),
+                other => prop_assert!(false, "{:?} from\n{}", other, written),
+            }
+        }
+
+        #[test]
+        fn can_read_written_planets(planet in planet()) {
+            let written = planet.to_string();
+            match read(&written) {
+                Ok(Object::Planet(parsed)) => prop_assert_eq!(parsed, planet),
+                other => prop_assert!(false, "{:?} from\n{}", other, written),
+            }
+        }
+
+        #[test]
+        fn can_read_written_systems(system in system()) {
+            let written = system.to_string();
+            match read(&written) {
+                Ok(Object::System(parsed)) => prop_assert_eq!(parsed, system),
+                other => prop_assert!(false, "{:?} from\n{}", other, written),
+            }
+        }
+
+        #[test]
+        fn can_read_written_ships(ship in ship()) {
+            let written = ship.to_string();
+            match read(&written) {
+                Ok(Object::Ship(parsed)) => prop_assert_eq!(parsed, ship),
+                other => prop_assert!(false, "{:?} from\n{}", other, written),
+            }
+        }
+    }
+}
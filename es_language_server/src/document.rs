@@ -0,0 +1,73 @@
+use lsp_types::{Position, Range};
+
+/// Offset in `text` of `slice`, which must be a slice of `text`
+pub fn offset_of(text: &str, slice: &str) -> usize {
+    (slice.as_ptr() as usize)
+        .saturating_sub(text.as_ptr() as usize)
+        .min(text.len())
+}
+
+/// Position of an offset, with columns counted in UTF-16 code units as LSP expects
+pub fn position_at(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// Offset of a position, clamped to its line
+pub fn offset_at(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(end) => line_start += end + 1,
+            None => return text.len(),
+        }
+    }
+    let line = text[line_start..].split('\n').next().unwrap_or("");
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    line_start + line.len()
+}
+
+/// Range covered by `slice`, which must be a slice of `text`
+pub fn range_of(text: &str, slice: &str) -> Range {
+    let start = offset_of(text, slice);
+    Range::new(
+        position_at(text, start),
+        position_at(text, start + slice.len()),
+    )
+}
+
+/// Range from an offset to the end of its line
+pub fn line_range_from(text: &str, offset: usize) -> Range {
+    let end = text[offset..]
+        .find('\n')
+        .map(|end| offset + end)
+        .unwrap_or_else(|| text.len());
+    Range::new(position_at(text, offset), position_at(text, end))
+}
+
+#[cfg(test)]
+mod test {
+    use lsp_types::Position;
+
+    use super::{offset_at, position_at};
+
+    #[test]
+    fn can_convert_positions() {
+        let text = "system Sol\n\tlink \"Âlpha\" x\n";
+        let offset = text.find(" x").unwrap();
+        let position = position_at(text, offset);
+        assert_eq!(position, Position::new(1, 13));
+        assert_eq!(offset_at(text, position), offset);
+        assert_eq!(offset_at(text, Position::new(5, 0)), text.len());
+    }
+}
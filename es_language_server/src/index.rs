@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+
+use es_data_parser::Node;
+use lsp_types::{Position, Range, Url};
+
+use crate::document::{offset_at, range_of};
+
+/// Where the names referenced by a key are
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// the first values of the line
+    Values(usize),
+    /// the first token of each line below
+    Children,
+}
+
+/// Keys referencing other objects: kind of the block, key, where the names are, and their kind.
+/// An empty key stands for the block itself.
+const REFERENCES: &[(&str, &str, Target, &str)] = &[
+    ("system", "link", Target::Values(1), "system"),
+    ("system", "object", Target::Values(1), "planet"),
+    ("system", "government", Target::Values(1), "government"),
+    ("system", "fleet", Target::Values(1), "fleet"),
+    ("system", "hazard", Target::Values(1), "hazard"),
+    ("planet", "government", Target::Values(1), "government"),
+    ("planet", "shipyard", Target::Values(1), "shipyard"),
+    ("planet", "outfitter", Target::Values(1), "outfitter"),
+    ("ship", "outfits", Target::Children, "outfit"),
+    ("shipyard", "", Target::Children, "ship"),
+    ("outfitter", "", Target::Children, "outfit"),
+    ("start", "system", Target::Values(1), "system"),
+    ("start", "planet", Target::Values(1), "planet"),
+    ("start", "ship", Target::Values(1), "ship"),
+    ("wormhole", "link", Target::Values(2), "system"),
+    ("wormhole", "color", Target::Values(1), "color"),
+];
+
+/// Keys known for each kind of block, offered as completions
+pub const KEYS: &[(&str, &[&str])] = &[
+    (
+        "ship",
+        &[
+            "sprite",
+            "thumbnail",
+            "attributes",
+            "outfits",
+            "engine",
+            "reverse engine",
+            "steering engine",
+            "gun",
+            "turret",
+            "bay",
+            "leak",
+            "explode",
+            "final explode",
+            "description",
+        ],
+    ),
+    (
+        "system",
+        &[
+            "pos",
+            "government",
+            "attributes",
+            "music",
+            "arrival",
+            "departure",
+            "habitable",
+            "belt",
+            "haze",
+            "ramscoop",
+            "invisible fence",
+            "jump range",
+            "starfield density",
+            "link",
+            "asteroids",
+            "minables",
+            "trade",
+            "fleet",
+            "raid",
+            "hazard",
+            "object",
+        ],
+    ),
+    (
+        "planet",
+        &[
+            "attributes",
+            "landscape",
+            "government",
+            "music",
+            "description",
+            "spaceport",
+            "shipyard",
+            "outfitter",
+            "bribe",
+            "security",
+            "tribute",
+            "required reputation",
+            "wormhole",
+        ],
+    ),
+    (
+        "start",
+        &["date", "system", "planet", "account", "set", "ship"],
+    ),
+    ("galaxy", &["pos", "sprite"]),
+    (
+        "effect",
+        &[
+            "sprite",
+            "sound",
+            "lifetime",
+            "random lifetime",
+            "velocity scale",
+            "random angle",
+            "random spin",
+            "random velocity",
+            "random frame rate",
+        ],
+    ),
+    (
+        "hazard",
+        &[
+            "weapon",
+            "constant strength",
+            "period",
+            "duration",
+            "strength",
+            "environmental effect",
+        ],
+    ),
+    ("news", &["location", "name", "portrait", "message"]),
+    ("wormhole", &["mappable", "link", "color"]),
+    (
+        "interface",
+        &[
+            "anchor",
+            "value",
+            "sprite",
+            "image",
+            "outline",
+            "label",
+            "string",
+            "button",
+            "bar",
+            "ring",
+            "line",
+            "box",
+            "visible if",
+            "active if",
+        ],
+    ),
+    ("trade", &["commodity"]),
+];
+
+/// A name in a document, either where an object is defined or where it is referenced
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub kind: String,
+    pub name: String,
+    pub uri: Url,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub symbol: Symbol,
+    /// first lines of the block defining the object
+    pub summary: String,
+}
+
+/// Definitions and references of all the documents, updated one document at a time
+#[derive(Debug, Default)]
+pub struct Index {
+    documents: BTreeMap<Url, DocumentIndex>,
+}
+
+#[derive(Debug, Default)]
+struct DocumentIndex {
+    definitions: Vec<Definition>,
+    references: Vec<Symbol>,
+}
+
+impl Index {
+    pub fn build(documents: &BTreeMap<Url, String>) -> Index {
+        let mut index = Index::default();
+        for (uri, text) in documents {
+            index.update(uri, text);
+        }
+        index
+    }
+
+    /// Index a document again, replacing what was known of it
+    pub fn update(&mut self, uri: &Url, text: &str) {
+        let mut document = DocumentIndex::default();
+        for block in es_data_parser::blocks(text).filter_map(Result::ok) {
+            if let Some(Ok(node)) = es_data_parser::nodes(block).next() {
+                document.add_block(uri, text, block, &node);
+            }
+        }
+        self.documents.insert(uri.clone(), document);
+    }
+
+    /// Forget a document
+    pub fn remove(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    /// Definitions of all the documents, in the order of their URIs
+    pub fn definitions(&self) -> impl Iterator<Item = &Definition> {
+        self.documents
+            .values()
+            .flat_map(|document| document.definitions.iter())
+    }
+
+    fn references(&self) -> impl Iterator<Item = &Symbol> {
+        self.documents
+            .values()
+            .flat_map(|document| document.references.iter())
+    }
+
+    /// Definition or reference at a position of a document
+    pub fn symbol_at(&self, uri: &Url, text: &str, position: Position) -> Option<&Symbol> {
+        let document = self.documents.get(uri)?;
+        let offset = offset_at(text, position);
+        let contains = |symbol: &&Symbol| {
+            offset_at(text, symbol.range.start) <= offset
+                && offset <= offset_at(text, symbol.range.end)
+        };
+        document
+            .definitions
+            .iter()
+            .map(|definition| &definition.symbol)
+            .find(contains)
+            .or_else(|| document.references.iter().find(contains))
+    }
+
+    pub fn definitions_of<'a>(
+        &'a self,
+        symbol: &'a Symbol,
+    ) -> impl Iterator<Item = &'a Definition> + 'a {
+        self.definitions().filter(move |definition| {
+            definition.symbol.kind == symbol.kind && definition.symbol.name == symbol.name
+        })
+    }
+
+    pub fn references_to<'a>(
+        &'a self,
+        symbol: &'a Symbol,
+    ) -> impl Iterator<Item = &'a Symbol> + 'a {
+        self.references()
+            .filter(move |reference| reference.kind == symbol.kind && reference.name == symbol.name)
+    }
+
+    /// Kind of the names expected after a key in a block of the given kind
+    pub fn reference_kind(block_kind: &str, key: &str) -> Option<&'static str> {
+        REFERENCES
+            .iter()
+            .find(|(kind, reference_key, target, _)| {
+                *kind == block_kind && *reference_key == key && matches!(target, Target::Values(_))
+            })
+            .map(|(_, _, _, target_kind)| *target_kind)
+    }
+}
+
+impl DocumentIndex {
+    fn add_block(&mut self, uri: &Url, text: &str, block: &str, node: &Node) {
+        let kind = node.tokens[0];
+        if let Some(name) = node.tokens.get(1) {
+            self.definitions.push(Definition {
+                symbol: Symbol {
+                    kind: String::from(kind),
+                    name: String::from(*name),
+                    uri: uri.clone(),
+                    range: range_of(text, name),
+                },
+                summary: block.lines().take(8).collect::<Vec<_>>().join("\n"),
+            });
+        }
+        self.add_references(uri, text, kind, node, true);
+    }
+
+    fn add_references(&mut self, uri: &Url, text: &str, kind: &str, node: &Node, root: bool) {
+        let key = if root { "" } else { node.tokens[0] };
+        for (_, _, target, target_kind) in
+            REFERENCES
+                .iter()
+                .filter(|(block_kind, reference_key, _, _)| {
+                    *block_kind == kind && *reference_key == key
+                })
+        {
+            let names = match target {
+                Target::Values(count) => node.tokens.iter().skip(1).take(*count).collect(),
+                Target::Children => node
+                    .children
+                    .iter()
+                    .map(|child| &child.tokens[0])
+                    .collect::<Vec<_>>(),
+            };
+            for name in names {
+                self.references.push(Symbol {
+                    kind: String::from(*target_kind),
+                    name: String::from(*name),
+                    uri: uri.clone(),
+                    range: range_of(text, name),
+                });
+            }
+        }
+        // keys are only those of the block itself, and of the objects of a system at any depth
+        for child in &node.children {
+            if root || (kind == "system" && key == "object" && child.tokens[0] == "object") {
+                self.add_references(uri, text, kind, child, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Language server for Endless Sky data files
+
+use std::error::Error;
+
+use lsp_server::Connection;
+use lsp_types::InitializeParams;
+
+mod document;
+mod index;
+mod server;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let params = connection.initialize(serde_json::to_value(server::capabilities())?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    server::Server::new(&params).run(&connection)?;
+    // the writer thread stops once the connection is dropped
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,388 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    LogMessage, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, References, Request as _};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, Diagnostic, DiagnosticSeverity,
+    GotoDefinitionParams, Hover, HoverContents, HoverParams, HoverProviderCapability,
+    InitializeParams, Location, LogMessageParams, MarkupContent, MarkupKind, MessageType, OneOf,
+    PublishDiagnosticsParams, ReferenceParams, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+
+use crate::document::{line_range_from, offset_at, offset_of};
+use crate::index::{Index, KEYS};
+
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(true),
+                })),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(Default::default()),
+        ..ServerCapabilities::default()
+    }
+}
+
+pub struct Server {
+    /// all known documents, open or read from the workspace
+    documents: BTreeMap<Url, String>,
+    /// definitions and references of the documents, updated as they change
+    index: Index,
+    /// problems met while reading the workspace, logged once the server runs
+    workspace_errors: Vec<String>,
+}
+
+impl Server {
+    pub fn new(params: &InitializeParams) -> Server {
+        let mut documents = BTreeMap::new();
+        #[allow(deprecated)]
+        let root = params.root_uri.as_ref();
+        let workspace_errors = match root.and_then(|root| root.to_file_path().ok()) {
+            Some(root) => read_workspace(&root, &mut documents),
+            None => vec![],
+        };
+        let index = Index::build(&documents);
+        Server {
+            documents,
+            index,
+            workspace_errors,
+        }
+    }
+
+    pub fn run(&mut self, connection: &Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+        for message in self.workspace_errors.drain(..) {
+            connection
+                .sender
+                .send(Message::Notification(Notification::new(
+                    LogMessage::METHOD.to_string(),
+                    LogMessageParams {
+                        typ: MessageType::ERROR,
+                        message,
+                    },
+                )))?;
+        }
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    connection
+                        .sender
+                        .send(Message::Response(self.handle_request(request)))?;
+                }
+                Message::Notification(notification) => {
+                    if let Some(uri) = self.handle_notification(notification) {
+                        connection
+                            .sender
+                            .send(Message::Notification(self.diagnostics(uri)))?;
+                    }
+                }
+                Message::Response(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            GotoDefinition::METHOD => {
+                respond(id, params(request).map(|params| self.definition(params)))
+            }
+            References::METHOD => {
+                respond(id, params(request).map(|params| self.references(params)))
+            }
+            HoverRequest::METHOD => respond(id, params(request).map(|params| self.hover(params))),
+            Completion::METHOD => {
+                respond(id, params(request).map(|params| self.completion(params)))
+            }
+            method => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown method {}", method),
+            ),
+        }
+    }
+
+    /// Update the documents, returning the document to check if it was opened, saved or closed
+    fn handle_notification(&mut self, notification: Notification) -> Option<Url> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                let uri = params.text_document.uri;
+                self.update(&uri, params.text_document.text);
+                Some(uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.update(&params.text_document.uri, change.text);
+                }
+                None
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: lsp_types::DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                let uri = params.text_document.uri;
+                if let Some(text) = params.text {
+                    self.update(&uri, text);
+                }
+                Some(uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params).ok()?;
+                let uri = params.text_document.uri;
+                // unsaved changes are dropped, and so is the document unless it is in the
+                // workspace
+                let saved = uri
+                    .to_file_path()
+                    .ok()
+                    .and_then(|path| fs::read_to_string(path).ok());
+                match saved {
+                    Some(text) => self.update(&uri, text),
+                    None => {
+                        self.documents.remove(&uri);
+                        self.index.remove(&uri);
+                    }
+                }
+                Some(uri)
+            }
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, uri: &Url, text: String) {
+        self.index.update(uri, &text);
+        self.documents.insert(uri.clone(), text);
+    }
+
+    fn diagnostics(&self, uri: Url) -> Notification {
+        let text = self.documents.get(&uri).map(String::as_str).unwrap_or("");
+        let mut diagnostics = vec![];
+        let mut report = |offset: usize, message: String| {
+            diagnostics.push(Diagnostic {
+                range: line_range_from(text, offset),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some(String::from("es_data_parser")),
+                message,
+                ..Diagnostic::default()
+            })
+        };
+        for block in es_data_parser::blocks(text) {
+            let block = match block {
+                Ok(block) => block,
+                Err(diagnostic) => {
+                    report(diagnostic.offset, diagnostic.message);
+                    continue;
+                }
+            };
+            // diagnostics of a block are relative to it
+            let block_offset = offset_of(text, block);
+            let kind = block.split_whitespace().next().unwrap_or("");
+            let error = match es_data_parser::nodes(block).next() {
+                Some(Err(diagnostic)) => Some(diagnostic),
+                _ if es_data_parser::OBJECT_KINDS.contains(&kind) => {
+                    es_data_parser::objects(block).next().and_then(Result::err)
+                }
+                _ => None,
+            };
+            if let Some(diagnostic) = error {
+                report(block_offset + diagnostic.offset, diagnostic.message);
+            }
+        }
+
+        Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            PublishDiagnosticsParams::new(uri, diagnostics, None),
+        )
+    }
+
+    fn text(&self, uri: &Url) -> &str {
+        self.documents.get(uri).map(String::as_str).unwrap_or("")
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Vec<Location> {
+        let position = params.text_document_position_params;
+        let uri = &position.text_document.uri;
+        self.index
+            .symbol_at(uri, self.text(uri), position.position)
+            .map(|symbol| {
+                self.index
+                    .definitions_of(symbol)
+                    .map(|definition| {
+                        Location::new(definition.symbol.uri.clone(), definition.symbol.range)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn references(&self, params: ReferenceParams) -> Vec<Location> {
+        let position = params.text_document_position;
+        let uri = &position.text_document.uri;
+        let symbol = match self.index.symbol_at(uri, self.text(uri), position.position) {
+            Some(symbol) => symbol,
+            None => return vec![],
+        };
+        let mut locations = vec![];
+        if params.context.include_declaration {
+            locations.extend(self.index.definitions_of(symbol).map(|definition| {
+                Location::new(definition.symbol.uri.clone(), definition.symbol.range)
+            }));
+        }
+        locations.extend(
+            self.index
+                .references_to(symbol)
+                .map(|reference| Location::new(reference.uri.clone(), reference.range)),
+        );
+        locations
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let uri = &position.text_document.uri;
+        let symbol = self
+            .index
+            .symbol_at(uri, self.text(uri), position.position)?;
+        let definition = self.index.definitions_of(symbol).last()?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```\n{}\n```", definition.summary),
+            }),
+            range: Some(symbol.range),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Vec<CompletionItem> {
+        let position = params.text_document_position;
+        let text = self.text(&position.text_document.uri);
+        let offset = offset_at(text, position.position);
+        let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let before = &text[line_start..offset];
+        let key = before.trim_start();
+        let kind = match block_kind(text, line_start) {
+            Some(kind) => kind,
+            None => return vec![],
+        };
+
+        if key.contains(char::is_whitespace) {
+            // completing a value: names of the objects expected by the key
+            let key = key.split_whitespace().next().unwrap_or("");
+            let target_kind = match Index::reference_kind(kind, key.trim_matches('"')) {
+                Some(target_kind) => target_kind,
+                None => return vec![],
+            };
+            // each name once, even if it is defined in several files
+            let names = self
+                .index
+                .definitions()
+                .filter(|definition| definition.symbol.kind == target_kind)
+                .map(|definition| definition.symbol.name.clone())
+                .collect::<BTreeSet<_>>();
+            names
+                .into_iter()
+                .map(|name| CompletionItem {
+                    insert_text: Some(quote(&name)),
+                    label: name,
+                    kind: Some(CompletionItemKind::REFERENCE),
+                    ..CompletionItem::default()
+                })
+                .collect()
+        } else if is_first_level(before) {
+            KEYS.iter()
+                .filter(|(block_kind, _)| *block_kind == kind)
+                .flat_map(|(_, keys)| keys.iter())
+                .map(|key| CompletionItem {
+                    label: String::from(*key),
+                    kind: Some(CompletionItemKind::PROPERTY),
+                    insert_text: Some(quote(key)),
+                    ..CompletionItem::default()
+                })
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+}
+
+fn respond<R: serde::Serialize>(id: RequestId, result: Result<R, serde_json::Error>) -> Response {
+    match result {
+        Ok(result) => Response::new_ok(id, result),
+        Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+    }
+}
+
+fn params<P: serde::de::DeserializeOwned>(request: Request) -> Result<P, serde_json::Error> {
+    serde_json::from_value(request.params)
+}
+
+/// Kind of the top-level block containing the line starting at `line_start`
+fn block_kind(text: &str, line_start: usize) -> Option<&str> {
+    text[..line_start]
+        .lines()
+        .rev()
+        .find(|line| {
+            !line.is_empty()
+                && !line.starts_with(char::is_whitespace)
+                && !line.starts_with('#')
+                && !line.starts_with("//")
+        })
+        .and_then(|line| line.split_whitespace().next())
+}
+
+fn is_first_level(indentation: &str) -> bool {
+    let indentation = indentation.trim_end_matches(|c: char| !c.is_whitespace());
+    indentation == "\t" || indentation == "    "
+}
+
+fn quote(token: &str) -> String {
+    if token.contains(char::is_whitespace) {
+        format!("\"{}\"", token)
+    } else {
+        String::from(token)
+    }
+}
+
+/// Read the data files of the workspace, returning what couldn't be read
+fn read_workspace(dir: &Path, documents: &mut BTreeMap<Url, String>) -> Vec<String> {
+    let error = |path: &Path, error| format!("could not read {}: {}", path.display(), error);
+    let files = match es_data_parser::data_files(dir, error) {
+        Ok(files) => files,
+        Err(error) => return vec![error],
+    };
+    let mut errors = vec![];
+    for path in files {
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                if let Ok(uri) = Url::from_file_path(&path) {
+                    documents.insert(uri, text);
+                }
+            }
+            Err(e) => errors.push(error(&path, e)),
+        }
+    }
+    errors
+}
@@ -0,0 +1,61 @@
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+
+const SESSION: &str = include_str!("fixtures/session.jsonl");
+const EXPECTED: &str = include_str!("fixtures/session.expected.jsonl");
+
+/// Run the server on a list of messages, one JSON message per line, and return the messages it
+/// sent back
+fn run_session(session: &str) -> Vec<serde_json::Value> {
+    let mut server = Command::new(env!("CARGO_BIN_EXE_es_language_server"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = server.stdin.take().unwrap();
+    for message in session.lines() {
+        write!(
+            stdin,
+            "Content-Length: {}\r\n\r\n{}",
+            message.len(),
+            message
+        )
+        .unwrap();
+    }
+    drop(stdin);
+
+    let mut output = String::new();
+    server
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut output)
+        .unwrap();
+    assert!(server.wait().unwrap().success());
+
+    let mut messages = vec![];
+    let mut output = output.as_str();
+    while let Some(header_end) = output.find("\r\n\r\n") {
+        let length = output[..header_end]
+            .lines()
+            .find_map(|header| header.strip_prefix("Content-Length: "))
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        let body = &output[header_end + 4..header_end + 4 + length];
+        messages.push(serde_json::from_str(body).unwrap());
+        output = &output[header_end + 4 + length..];
+    }
+    messages
+}
+
+#[test]
+fn can_answer_a_session() {
+    let expected = EXPECTED
+        .lines()
+        .map(|message| serde_json::from_str::<serde_json::Value>(message).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(run_session(SESSION), expected);
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use gdnative::*;
 
 type OwnerNode = Node2D;
@@ -29,16 +31,51 @@ impl Game {
     #[export]
     fn _ready(&mut self, mut owner: OwnerNode) {
         let mut game_data_file = gdnative::File::new();
-        game_data_file
+        if game_data_file
             .open("res://data/simple_game.txt".into(), 1)
-            .unwrap();
-        let game_data = game_data::start_from_es_data(&game_data_file.get_as_text().to_string());
+            .is_err()
+        {
+            show_error(owner, "could not open res://data/simple_game.txt");
+            return;
+        }
+        let game_data =
+            match game_data::start_from_es_data(&game_data_file.get_as_text().to_string()) {
+                Ok(game_data) => game_data,
+                Err(error) => {
+                    show_error(owner, &format!("could not load the game data: {}", error));
+                    return;
+                }
+            };
+        let mut images = BTreeSet::new();
+        image_files("res://images", "", &mut images);
+        let assets = game_data::Assets::from_files(images);
+        for unresolved in assets.unresolved(&game_data) {
+            godot_print!(
+                "bad images for sprite {} of {:?} {}: {:?}",
+                unresolved.sprite,
+                unresolved.kind,
+                unresolved.name,
+                unresolved.problem
+            );
+        }
         let mut object_parent = unsafe {
             owner
                 .get_node("objects".into())
                 .expect("objects is present")
         };
-        game_data.systems[0].objects.iter().for_each(|object| {
+        let system = match game_data.systems.get(game_data.player.system) {
+            Some(system) => system,
+            None => {
+                show_error(
+                    owner,
+                    &format!("unknown system {:?}", game_data.player.system),
+                );
+                return;
+            }
+        };
+        let days = game_data::days_since_epoch(&game_data.player.date);
+        let positions = system.positions_at(days);
+        positions.iter().for_each(|(object, position)| {
             if let Some(mut new_stellar_object) = self
                 .star_scene
                 .as_ref()
@@ -46,10 +83,15 @@ impl Game {
                 .and_then(|new_node| unsafe { new_node.cast::<Node2D>() })
             {
                 unsafe {
-                    if let Some(ref sprite) = object.sprite {
+                    if let Some(image) = object
+                        .sprite
+                        .as_ref()
+                        .and_then(|sprite| assets.frame_at(sprite, 0.0))
+                        .and_then(|frame| frame.image(false))
+                    {
                         let texture = ResourceLoader::godot_singleton()
                             .load(
-                                format!("res://images/{}.png", sprite).into(),
+                                format!("res://images/{}", image.display()).into(),
                                 "Texture".into(),
                                 false,
                             )
@@ -61,7 +103,8 @@ impl Game {
                             .unwrap()
                             .set_texture(texture);
                     }
-                    new_stellar_object.translate(euclid::vec2(0.0, object.distance));
+                    new_stellar_object
+                        .translate(euclid::vec2(position.x as f32, position.y as f32));
                     object_parent.add_child(Some(new_stellar_object.to_node()), false);
                 }
             };
@@ -112,3 +155,49 @@ impl Game {
         }
     }
 }
+
+/// Show an error in place of the game
+fn show_error(owner: OwnerNode, message: &str) {
+    godot_print!("{}", message);
+    unsafe {
+        if let Some(mut label) = owner
+            .get_node("error".into())
+            .and_then(|node| node.cast::<Label>())
+        {
+            label.set_text(message.into());
+            label.set_visible(true);
+        }
+    }
+}
+
+/// List the images below a resource directory, relative to it
+///
+/// This goes through Godot rather than the file system, as exported games have their images in
+/// a pack. There, only the `.import` files are listed, named after the image they were imported
+/// from.
+fn image_files(root: &str, dir: &str, files: &mut BTreeSet<String>) {
+    let mut directory = Directory::new();
+    if directory.open(format!("{}/{}", root, dir).into()).is_err()
+        || directory.list_dir_begin(true, true).is_err()
+    {
+        godot_print!("could not list the images in {}/{}", root, dir);
+        return;
+    }
+    loop {
+        let name = directory.get_next().to_string();
+        if name.is_empty() {
+            break;
+        }
+        let path = if dir.is_empty() {
+            name
+        } else {
+            format!("{}/{}", dir, name)
+        };
+        if directory.current_is_dir() {
+            image_files(root, &path, files);
+        } else {
+            files.insert(String::from(path.trim_end_matches(".import")));
+        }
+    }
+    directory.list_dir_end();
+}
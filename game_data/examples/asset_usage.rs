@@ -0,0 +1,62 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use es_data_parser::{data_files, nodes};
+use game_data::{image_references, Assets};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <data> <images directory>", args[0]);
+        process::exit(2);
+    }
+
+    let error = |path: &Path, error| format!("{}: {}", path.display(), error);
+    let sources = data_files(&args[1], error).and_then(|files| {
+        files
+            .into_iter()
+            .map(|file| {
+                let source = fs::read_to_string(&file).map_err(|e| error(&file, e))?;
+                Ok((file, source))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    });
+    let sources = sources.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+
+    let mut references = vec![];
+    for (file, source) in &sources {
+        let mut file_nodes = vec![];
+        for node in nodes(source) {
+            match node {
+                Ok(node) => file_nodes.push(node),
+                Err(diagnostic) => eprintln!("{}:{}", file.display(), diagnostic),
+            }
+        }
+        references.extend(image_references(&file_nodes));
+    }
+
+    let assets = Assets::from_dir(&args[2]).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    let usage = assets.usage(&references);
+    for reference in &usage.missing {
+        println!(
+            "missing {} ({} of {} \"{}\")",
+            reference.image, reference.key, reference.kind, reference.name
+        );
+    }
+    for image in &usage.unreferenced {
+        println!("unreferenced {}", image.display());
+    }
+    eprintln!(
+        "{} missing, {} unreferenced",
+        usage.missing.len(),
+        usage.unreferenced.len()
+    );
+}
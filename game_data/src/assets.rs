@@ -0,0 +1,561 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::io_error;
+use crate::{DataKind, Game, GameDataError, Object, Sprite, Symbol};
+
+/// How a sprite is blended, from the character before the frame number of its files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BlendMode {
+    /// `-`, or a sprite without frame number
+    Alpha,
+    /// `=`, the image is already premultiplied
+    Premultiplied,
+    /// `+`
+    Additive,
+    /// `~`
+    HalfAdditive,
+}
+
+impl BlendMode {
+    fn from_separator(separator: char) -> Option<BlendMode> {
+        match separator {
+            '-' => Some(BlendMode::Alpha),
+            '=' => Some(BlendMode::Premultiplied),
+            '+' => Some(BlendMode::Additive),
+            '~' => Some(BlendMode::HalfAdditive),
+            _ => None,
+        }
+    }
+}
+
+/// Image files of one frame, relative to the images directory
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Frame {
+    pub path: Option<PathBuf>,
+    pub high_dpi: Option<PathBuf>,
+}
+
+impl Frame {
+    /// Image to use, the high DPI one if asked and available
+    pub fn image(&self, high_dpi: bool) -> Option<&Path> {
+        match (&self.path, &self.high_dpi) {
+            (_, Some(path)) if high_dpi => Some(path),
+            (Some(path), _) | (None, Some(path)) => Some(path),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteImages {
+    pub blend_mode: BlendMode,
+    /// frames by their number, a frame without file leaving an empty frame
+    pub frames: Vec<Frame>,
+    /// whether files of different frames use different blending characters
+    pub mixed_blend_modes: bool,
+}
+
+impl SpriteImages {
+    /// Numbers of the frames without image
+    pub fn missing_frames(&self) -> Vec<u32> {
+        (0..)
+            .zip(&self.frames)
+            .filter(|(_, frame)| frame.image(false).is_none())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Why these images can't be used as is
+    pub fn problem(&self) -> Option<SpriteProblem> {
+        let missing_frames = self.missing_frames();
+        if !missing_frames.is_empty() {
+            Some(SpriteProblem::MissingFrames(missing_frames))
+        } else if self.mixed_blend_modes {
+            Some(SpriteProblem::MixedBlendModes)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpriteProblem {
+    /// no image for the sprite
+    Missing,
+    /// frames without image, like frame 2 of `-0`, `-1` and `-3`
+    MissingFrames(Vec<u32>),
+    /// files with different blending characters, like `-0` and `+1`
+    MixedBlendModes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedSprite {
+    pub sprite: String,
+    pub kind: DataKind,
+    pub name: Symbol,
+    pub problem: SpriteProblem,
+}
+
+/// An image referenced in the data, like the sprite of a ship
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageReference {
+    pub image: String,
+    /// key referencing the image, like `sprite` or `landscape`
+    pub key: &'static str,
+    /// kind and name of the top-level object referencing the image
+    pub kind: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AssetUsage {
+    pub missing: Vec<ImageReference>,
+    /// image files no reference resolves to, relative to the images directory
+    pub unreferenced: Vec<PathBuf>,
+}
+
+/// Keys whose value is an image, including the flares of engines and the sprites of hardpoints
+/// in ships and outfits, and the images of interfaces
+const IMAGE_KEYS: &[&str] = &[
+    "sprite",
+    "thumbnail",
+    "landscape",
+    "haze",
+    "flare sprite",
+    "reverse flare sprite",
+    "steering flare sprite",
+    "hardpoint sprite",
+    "image",
+    "outline",
+];
+
+/// Images referenced by top-level nodes of data files, at any depth
+pub fn image_references(nodes: &[es_data_parser::Node]) -> Vec<ImageReference> {
+    fn walk(
+        node: &es_data_parser::Node,
+        kind: &str,
+        name: &str,
+        references: &mut Vec<ImageReference>,
+    ) {
+        if let (Some(key), Some(image)) = (
+            IMAGE_KEYS.iter().find(|key| **key == node.tokens[0]),
+            node.tokens.get(1),
+        ) {
+            references.push(ImageReference {
+                image: String::from(*image),
+                key,
+                kind: String::from(kind),
+                name: String::from(name),
+            });
+        }
+        for child in &node.children {
+            walk(child, kind, name, references);
+        }
+    }
+
+    let mut references = vec![];
+    for node in nodes {
+        let name = node.tokens[1..].join(" ");
+        for child in &node.children {
+            walk(child, node.tokens[0], &name, &mut references);
+        }
+    }
+    references
+}
+
+/// Images available for sprites, by sprite name
+#[derive(Debug, Clone, Default)]
+pub struct Assets {
+    pub sprites: HashMap<String, SpriteImages>,
+}
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+/// Files with a higher frame number are ignored, rather than filling the gap up to them
+const MAX_FRAMES: u32 = 10_000;
+
+/// How many files of a sprite use each blending mode, and its frames by number
+type SpriteFiles = (BTreeMap<BlendMode, usize>, BTreeMap<u32, Frame>);
+
+impl Assets {
+    /// Index all the images of a directory, like the `images` directory of Endless Sky
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Assets, GameDataError> {
+        let root = path.as_ref();
+        let mut files = vec![];
+        image_files(root, &mut files)?;
+        Ok(Assets::from_files(
+            files.iter().filter_map(|file| file.strip_prefix(root).ok()),
+        ))
+    }
+
+    /// Index image files, given by their path relative to the images directory
+    ///
+    /// A sprite `ship/shuttle` can be a single image `ship/shuttle.png`, or frames like
+    /// `ship/shuttle+0.png`, `ship/shuttle+1.png`, where the character before the frame number
+    /// gives the blending mode. Each image can have a high DPI version, like `ship/shuttle@2x.png`.
+    pub fn from_files<P: AsRef<Path>>(files: impl IntoIterator<Item = P>) -> Assets {
+        let mut sprites: HashMap<String, SpriteFiles> = HashMap::new();
+        for file in files {
+            let file = file.as_ref();
+            let extension = file.extension().and_then(|extension| extension.to_str());
+            if !extension.is_some_and(|extension| {
+                IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            }) {
+                continue;
+            }
+            let stem = match file.with_extension("").to_str() {
+                Some(stem) => stem.replace('\\', "/"),
+                None => continue,
+            };
+            let (stem, high_dpi) = match stem.strip_suffix("@2x") {
+                Some(stem) => (stem, true),
+                None => (stem.as_str(), false),
+            };
+            let (name, blend_mode, index) = split_frame(stem);
+            if index >= MAX_FRAMES {
+                continue;
+            }
+
+            let (blend_modes, frames) = sprites.entry(String::from(name)).or_default();
+            *blend_modes.entry(blend_mode).or_default() += 1;
+            let frame = frames.entry(index).or_default();
+            if high_dpi {
+                frame.high_dpi = Some(file.to_path_buf());
+            } else {
+                frame.path = Some(file.to_path_buf());
+            }
+        }
+
+        Assets {
+            sprites: sprites
+                .into_iter()
+                .map(|(name, (blend_modes, mut frames))| {
+                    // the blending character used by the most files
+                    let blend_mode = blend_modes
+                        .iter()
+                        .max_by_key(|(blend_mode, count)| {
+                            (**count, std::cmp::Reverse(**blend_mode))
+                        })
+                        .map(|(blend_mode, _)| *blend_mode)
+                        .unwrap_or(BlendMode::Alpha);
+                    let count = frames.keys().next_back().map_or(0, |last| last + 1);
+                    let frames = (0..count)
+                        .map(|index| frames.remove(&index).unwrap_or_default())
+                        .collect();
+                    (
+                        name,
+                        SpriteImages {
+                            blend_mode,
+                            frames,
+                            mixed_blend_modes: blend_modes.len() > 1,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn resolve(&self, sprite: &str) -> Option<&SpriteImages> {
+        self.sprites.get(sprite)
+    }
+
+    /// Frame of a sprite to show `time` seconds after its animation started
+    pub fn frame_at(&self, sprite: &Sprite, time: f32) -> Option<&Frame> {
+        let images = self.resolve(&sprite.name)?;
+        let frame = sprite.animation.frame_at(time, images.frames.len() as u32);
+        images.frames.get(frame as usize)
+    }
+
+    /// References without image, and images without reference
+    pub fn usage(&self, references: &[ImageReference]) -> AssetUsage {
+        let missing = references
+            .iter()
+            .filter(|reference| self.resolve(&reference.image).is_none())
+            .cloned()
+            .collect();
+        let mut unreferenced = self
+            .sprites
+            .iter()
+            .filter(|(sprite, _)| {
+                !references
+                    .iter()
+                    .any(|reference| reference.image == **sprite)
+            })
+            .flat_map(|(_, images)| images.frames.iter())
+            .flat_map(|frame| frame.path.iter().chain(frame.high_dpi.iter()))
+            .cloned()
+            .collect::<Vec<_>>();
+        unreferenced.sort();
+        AssetUsage {
+            missing,
+            unreferenced,
+        }
+    }
+
+    /// Sprites and landscapes used by the game that have no image, or frames missing or with
+    /// mixed blending modes
+    pub fn unresolved(&self, game: &Game) -> Vec<UnresolvedSprite> {
+        let ships = game
+            .ships
+            .iter()
+            .map(|ship| (ship.sprite.name.as_str(), DataKind::Ship, ship.name));
+        let systems = game.systems.iter().flat_map(|system| {
+            let mut sprites = vec![];
+            object_sprites(&system.objects, &mut sprites);
+            sprites
+                .into_iter()
+                .map(move |sprite| (sprite, DataKind::System, system.name))
+        });
+        let planets = game.planets.iter().filter_map(|planet| {
+            planet
+                .landscape
+                .as_ref()
+                .map(|landscape| (landscape.as_str(), DataKind::Planet, planet.name))
+        });
+
+        ships
+            .chain(systems)
+            .chain(planets)
+            .filter_map(|(sprite, kind, name)| {
+                let problem = match self.resolve(sprite) {
+                    Some(images) => images.problem()?,
+                    None => SpriteProblem::Missing,
+                };
+                Some(UnresolvedSprite {
+                    sprite: String::from(sprite),
+                    kind,
+                    name,
+                    problem,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Sprites of objects and their moons
+fn object_sprites<'a>(objects: &'a [Object], sprites: &mut Vec<&'a str>) {
+    for object in objects {
+        if let Some(sprite) = &object.sprite {
+            sprites.push(&sprite.name);
+        }
+        object_sprites(&object.children, sprites);
+    }
+}
+
+/// Split a file stem in its sprite name, blending mode and frame number
+fn split_frame(stem: &str) -> (&str, BlendMode, u32) {
+    let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        let separator_at = stem.len() - digits - 1;
+        if let (Some(blend_mode), Ok(index)) = (
+            stem[separator_at..]
+                .chars()
+                .next()
+                .and_then(BlendMode::from_separator),
+            stem[separator_at + 1..].parse(),
+        ) {
+            return (&stem[..separator_at], blend_mode, index);
+        }
+    }
+    (stem, BlendMode::Alpha, 0)
+}
+
+fn image_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), GameDataError> {
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        if path.is_dir() {
+            image_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use es_data_parser::nodes;
+
+    use super::image_references;
+    use crate::{
+        start_from_es_data, Assets, BlendMode, DataKind, Sprite, SpriteAnimation, SpriteProblem,
+        Symbol,
+    };
+
+    #[test]
+    fn can_resolve_sprites() {
+        let assets = Assets::from_files(vec![
+            "ship/shuttle+1.png",
+            "ship/shuttle+0.png",
+            "ship/shuttle+0@2x.png",
+            "planet/rock13-b.png",
+            "planet/rock13-b.png.import",
+            "star/g5@2x.png",
+        ]);
+
+        let shuttle = assets.resolve("ship/shuttle").unwrap();
+        assert_eq!(shuttle.blend_mode, BlendMode::Additive);
+        assert_eq!(shuttle.frames.len(), 2);
+        assert_eq!(
+            shuttle.frames[0].image(true),
+            Some(Path::new("ship/shuttle+0@2x.png"))
+        );
+        assert_eq!(
+            shuttle.frames[1].image(true),
+            Some(Path::new("ship/shuttle+1.png"))
+        );
+
+        let rock = assets.resolve("planet/rock13-b").unwrap();
+        assert_eq!(rock.blend_mode, BlendMode::Alpha);
+        assert_eq!(
+            rock.frames[0].image(false),
+            Some(Path::new("planet/rock13-b.png"))
+        );
+        assert_eq!(
+            assets.resolve("star/g5").unwrap().frames[0].image(false),
+            Some(Path::new("star/g5@2x.png"))
+        );
+        assert_eq!(assets.resolve("planet/rock13-b.png"), None);
+        assert_eq!(shuttle.problem(), None);
+
+        let sprite = Sprite {
+            name: String::from("ship/shuttle"),
+            animation: SpriteAnimation {
+                frame_rate: 1.,
+                ..SpriteAnimation::default()
+            },
+        };
+        assert_eq!(
+            assets
+                .frame_at(&sprite, 1.5)
+                .and_then(|frame| frame.image(false)),
+            Some(Path::new("ship/shuttle+1.png"))
+        );
+    }
+
+    #[test]
+    fn can_report_asset_usage() {
+        let data = r#"ship "Shuttle"
+	sprite "ship/shuttle"
+		"frame time" 4
+	thumbnail "thumbnail/shuttle"
+	"flare sprite" "effect/flare/small"
+		"frame rate" 2
+system Sol
+	haze _menu/haze
+	object Earth
+		sprite planet/earth
+		object
+			sprite planet/moon
+planet Earth
+	landscape land/sea
+interface "menu"
+	image "ui/logo"
+	outline "ship/shuttle"
+"#;
+        let nodes = nodes(data).collect::<Result<Vec<_>, _>>().unwrap();
+        let references = image_references(&nodes);
+        assert_eq!(
+            references
+                .iter()
+                .map(|reference| (reference.key, reference.image.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("sprite", "ship/shuttle"),
+                ("thumbnail", "thumbnail/shuttle"),
+                ("flare sprite", "effect/flare/small"),
+                ("haze", "_menu/haze"),
+                ("sprite", "planet/earth"),
+                ("sprite", "planet/moon"),
+                ("landscape", "land/sea"),
+                ("image", "ui/logo"),
+                ("outline", "ship/shuttle"),
+            ]
+        );
+        assert_eq!(references[5].kind, "system");
+        assert_eq!(references[5].name, "Sol");
+
+        let assets = Assets::from_files(vec![
+            "ship/shuttle=0.png",
+            "ship/shuttle=1.png",
+            "thumbnail/shuttle.png",
+            "planet/earth.png",
+            "planet/earth@2x.png",
+            "planet/mars.png",
+            "planet/mars@2x.png",
+            "land/sea.jpg",
+            "_menu/haze.png",
+            "effect/flare/small.png",
+            "ui/logo.png",
+        ]);
+        let usage = assets.usage(&references);
+        assert_eq!(usage.missing, vec![references[5].clone()]);
+        assert_eq!(
+            usage.unreferenced,
+            vec![
+                PathBuf::from("planet/mars.png"),
+                PathBuf::from("planet/mars@2x.png")
+            ]
+        );
+    }
+
+    #[test]
+    fn can_report_unresolved_sprites() {
+        let game = start_from_es_data(include_str!("../../data/simple_game.txt")).unwrap();
+        let assets = Assets::from_files(vec![
+            "ship/shuttle=0.png",
+            "ship/shuttle=1.png",
+            "ship/shuttle=3.png",
+            "star/g5.png",
+            "planet/rock6.png",
+            "planet/cloud6.png",
+            "planet/rock13-b.png",
+            "planet/ice7-b.png",
+            "planet/gas16-b.png",
+            "planet/ganymede.png",
+            "land/water2.jpg",
+        ]);
+
+        let unresolved = assets.unresolved(&game);
+        assert_eq!(unresolved.len(), 3);
+        assert_eq!(unresolved[0].sprite, "ship/shuttle");
+        assert_eq!(unresolved[0].problem, SpriteProblem::MissingFrames(vec![2]));
+        assert_eq!(unresolved[1].sprite, "planet/gas14");
+        assert_eq!(unresolved[1].kind, DataKind::System);
+        assert_eq!(unresolved[1].name, Symbol::intern("Rutilicus"));
+        assert_eq!(unresolved[1].problem, SpriteProblem::Missing);
+        // moons are checked too
+        assert_eq!(unresolved[2].sprite, "planet/rock0");
+    }
+
+    #[test]
+    fn can_keep_frame_numbers() {
+        let assets = Assets::from_files(vec![
+            "effect/spark-0.png",
+            "effect/spark-1.png",
+            "effect/spark+3.png",
+            "effect/spark-4.png",
+            "effect/spark-99999.png",
+        ]);
+
+        let spark = assets.resolve("effect/spark").unwrap();
+        assert_eq!(spark.blend_mode, BlendMode::Alpha);
+        assert_eq!(spark.frames.len(), 5);
+        assert_eq!(
+            spark.frames[3].image(false),
+            Some(Path::new("effect/spark+3.png"))
+        );
+        assert_eq!(spark.frames[2].image(false), None);
+        assert_eq!(spark.missing_frames(), vec![2]);
+        assert!(spark.mixed_blend_modes);
+
+        let assets = Assets::from_files(vec!["effect/spark-0.png", "effect/spark+1.png"]);
+        assert_eq!(
+            assets.resolve("effect/spark").unwrap().problem(),
+            Some(SpriteProblem::MixedBlendModes)
+        );
+    }
+}
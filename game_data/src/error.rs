@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use es_data_parser::Diagnostic;
+
+use crate::{DataKind, Symbol};
+
+/// Why a game could not be built from its data
+#[derive(Debug)]
+pub enum GameDataError {
+    /// a file or directory could not be read
+    Io { path: PathBuf, error: io::Error },
+    /// blocks of a data file could not be parsed
+    Parse {
+        path: PathBuf,
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// a save game could not be parsed
+    InvalidSave(Diagnostic),
+    /// something used by the game is not defined in the data
+    MissingReference { kind: DataKind, name: Symbol },
+    /// the data has no start, or none with the requested name
+    NoStart(Option<Symbol>),
+    /// a name can't be written in a data file, as it has both quotes and backticks, or line
+    /// breaks
+    Unwritable(String),
+}
+
+impl fmt::Display for GameDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameDataError::Io { path, error } => {
+                write!(f, "could not read {}: {}", path.display(), error)
+            }
+            GameDataError::Parse { path, diagnostics } => {
+                write!(f, "could not parse {}", path.display())?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n{}:{}", path.display(), diagnostic)?;
+                }
+                Ok(())
+            }
+            GameDataError::InvalidSave(diagnostic) => {
+                write!(f, "could not parse the save game\n{}", diagnostic)
+            }
+            GameDataError::MissingReference { kind, name } => {
+                write!(f, "unknown {:?} {:?}", kind, name)
+            }
+            GameDataError::NoStart(None) => write!(f, "no start defined"),
+            GameDataError::NoStart(Some(name)) => write!(f, "no start named {:?}", name),
+            GameDataError::Unwritable(value) => write!(f, "can't write {:?}", value),
+        }
+    }
+}
+
+impl Error for GameDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GameDataError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Wrap an IO error with the path that couldn't be read
+pub(crate) fn io_error(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> GameDataError {
+    let path = path.into();
+    move |error| GameDataError::Io { path, error }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use crate::{start_from_es, start_from_es_data, GameDataError};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    #[test]
+    fn can_report_errors() {
+        let error = start_from_es("does/not/exist.txt").unwrap_err();
+        assert!(matches!(
+            &error,
+            GameDataError::Io { path, .. } if path == Path::new("does/not/exist.txt")
+        ));
+        assert!(std::error::Error::source(&error).is_some());
+
+        let data = format!("  indented\n{}system Broken\n\tpos a b\n", ES_DATA);
+        match start_from_es_data(&data) {
+            Err(GameDataError::Parse { diagnostics, .. }) => {
+                assert_eq!(diagnostics.len(), 2);
+                assert_eq!(diagnostics[0].message, "indented line outside of an object");
+                assert!(diagnostics[1].line > 1);
+            }
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+
+        let data = &ES_DATA[ES_DATA.find("\nsystem").unwrap()..];
+        assert!(matches!(
+            start_from_es_data(data),
+            Err(GameDataError::NoStart(None))
+        ));
+        assert_eq!(GameDataError::NoStart(None).to_string(), "no start defined");
+    }
+
+    #[test]
+    fn will_skip_blocks_not_used_by_the_game() {
+        let data = format!(
+            "{}mission \"Intro\"\n\tlanding\noutfit \"Hyperdrive\"\n\t\"hyperdrive\" 1\n",
+            ES_DATA
+        );
+        let game = start_from_es_data(&data).unwrap();
+        assert_eq!(
+            game.systems.len(),
+            start_from_es_data(ES_DATA).unwrap().systems.len()
+        );
+    }
+}
@@ -1,98 +1,337 @@
 use std::fs;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-#[derive(Debug)]
+pub use es_data_parser::{Account, Date, Mortgage, Position, Ramscoop, SpriteAnimation};
+
+mod assets;
+pub use assets::{
+    image_references, AssetUsage, Assets, BlendMode, Frame, ImageReference, SpriteImages,
+    SpriteProblem, UnresolvedSprite,
+};
+mod error;
+pub use error::GameDataError;
+mod loader;
+pub use loader::start_from_es_dir;
+mod map;
+pub use map::{Drive, GalaxyMap};
+mod orbit;
+pub use orbit::days_since_epoch;
+mod reload;
+pub use reload::{Change, DataKind};
+mod registry;
+pub use registry::{Named, Registry};
+mod save;
+mod start;
+mod symbol;
+pub use symbol::Symbol;
+mod system;
+mod travel;
+pub use travel::{Hop, RoutePlan};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sprite {
+    pub name: String,
+    pub animation: SpriteAnimation,
+}
+
+impl From<&es_data_parser::Sprite<'_>> for Sprite {
+    fn from(sprite: &es_data_parser::Sprite) -> Sprite {
+        Sprite {
+            name: String::from(sprite.name),
+            animation: sprite.animation,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Ship {
+    pub name: Symbol,
+    pub sprite: Sprite,
+    pub fuel_capacity: u32,
+    pub outfits: Vec<(Symbol, u32)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlayerShip {
     pub name: String,
-    pub sprite: String,
+    pub model: Arc<Ship>,
+    pub outfits: Vec<(Symbol, u32)>,
+    pub system: Option<Symbol>,
+    pub planet: Option<Symbol>,
+    pub parked: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Player {
-    pub ship: Arc<Ship>,
+    pub first_name: String,
+    pub last_name: String,
+    pub date: Date,
+    pub system: Symbol,
+    pub planet: Option<Symbol>,
+    pub account: Account,
+    /// debts other than the mortgage, like fines, with their kind
+    pub debts: Vec<(String, Mortgage)>,
+    pub fleet: Vec<PlayerShip>,
+    pub visited: Vec<Symbol>,
+    pub conditions: Vec<(String, i64)>,
 }
 
-#[derive(Debug)]
+impl Player {
+    pub fn flagship(&self) -> Option<&PlayerShip> {
+        self.fleet.first()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct Object {
-    pub sprite: Option<String>,
+    pub name: Option<Symbol>,
+    pub sprite: Option<Sprite>,
     pub distance: f32,
     pub period: f32,
+    pub offset: f32,
+    pub children: Vec<Object>,
 }
 
-#[derive(Debug)]
+impl From<&es_data_parser::SystemObject<'_>> for Object {
+    fn from(object: &es_data_parser::SystemObject) -> Object {
+        Object {
+            name: object.name.map(Symbol::intern),
+            sprite: object.sprite.as_ref().map(Sprite::from),
+            distance: object.distance.unwrap_or(0.0),
+            period: object.period,
+            offset: object.offset.unwrap_or(0.0),
+            children: object.objects.iter().map(Object::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct System {
-    pub name: String,
+    pub name: Symbol,
+    pub pos: Position,
+    pub government: Symbol,
+    pub links: Vec<Symbol>,
+    pub jump_range: Option<f32>,
+    pub ramscoop: Option<Ramscoop>,
     pub objects: Vec<Object>,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct Planet {
+    pub name: Symbol,
+    pub government: Option<Symbol>,
+    pub landscape: Option<String>,
+    pub description: Vec<String>,
+    pub spaceport: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Start {
+    /// empty for a start without name
+    pub name: Symbol,
+    pub date: Date,
+    pub system: Symbol,
+    pub planet: Symbol,
+    pub account: Account,
+    pub set: String,
+    /// models of the ships, with the names given to them
+    pub ships: Vec<(Symbol, Option<String>)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DataFile {
+    pub path: PathBuf,
+    pub systems: Vec<System>,
+    pub planets: Vec<Planet>,
+    pub ships: Vec<Arc<Ship>>,
+    pub starts: Vec<Start>,
+    /// links through wormholes, from the system where they are entered to the one they lead to
+    pub wormholes: Vec<(Symbol, Symbol)>,
+}
+
 #[derive(Debug)]
 pub struct Game {
     pub player: Player,
-    pub systems: Vec<System>,
-    pub ships: Vec<Arc<Ship>>,
+    pub systems: Registry<System>,
+    pub planets: Registry<Planet>,
+    pub ships: Registry<Arc<Ship>>,
+    pub starts: Registry<Start>,
+    pub wormholes: Vec<(Symbol, Symbol)>,
+    pub files: Vec<DataFile>,
 }
 
-pub fn start_from_es(path: &str) -> Game {
-    let es_game_data_source =
-        fs::read_to_string(path).expect("Something went wrong reading the file");
+pub fn start_from_es(path: &str) -> Result<Game, GameDataError> {
+    let es_game_data_source = fs::read_to_string(path).map_err(error::io_error(path))?;
 
-    start_from_es_data(&es_game_data_source)
+    from_data_files(vec![DataFile::parse(path, &es_game_data_source)?])
 }
 
-pub fn start_from_es_data(es_game_data_source: &str) -> Game {
-    let es_game_data = es_data_parser::parse(es_game_data_source);
+pub fn start_from_es_data(es_game_data_source: &str) -> Result<Game, GameDataError> {
+    from_data_files(vec![DataFile::parse("", es_game_data_source)?])
+}
 
-    let ships = es_game_data
-        .iter()
-        .filter_map(|object| {
-            if let es_data_parser::Object::Ship(ship) = object {
-                Some(ship)
-            } else {
-                None
+impl DataFile {
+    /// Parse a data file, failing with the diagnostics of all the blocks that can't be parsed
+    ///
+    /// Blocks of kinds that aren't used by the game, like missions or outfits, are skipped.
+    pub(crate) fn parse(path: impl Into<PathBuf>, source: &str) -> Result<DataFile, GameDataError> {
+        let mut objects = vec![];
+        let mut diagnostics = vec![];
+        for object in es_data_parser::known_objects(source) {
+            match object {
+                Ok(object) => objects.push(object),
+                Err(diagnostic) => diagnostics.push(diagnostic),
             }
-        })
-        .map(|ship| {
-            Arc::new(Ship {
-                name: String::from(ship.name),
-                sprite: match ship.sprite {
-                    es_data_parser::Sprite::Simple(sprite) => String::from(sprite),
-                    es_data_parser::Sprite::Sprite { name, .. } => format!("{}=0", name),
-                },
+        }
+        if diagnostics.is_empty() {
+            Ok(DataFile::from_es_objects(path, &objects))
+        } else {
+            Err(GameDataError::Parse {
+                path: path.into(),
+                diagnostics,
             })
-        })
-        .collect::<Vec<_>>();
-
-    let systems = es_game_data
-        .iter()
-        .filter_map(|object| {
-            if let es_data_parser::Object::System(system) = object {
-                Some(system)
-            } else {
-                None
-            }
-        })
-        .map(|system| System {
-            name: String::from(system.name),
-            objects: system
-                .objects
+        }
+    }
+
+    pub(crate) fn from_es_objects(
+        path: impl Into<PathBuf>,
+        es_game_data: &[es_data_parser::Object],
+    ) -> DataFile {
+        let mut file = DataFile {
+            path: path.into(),
+            systems: vec![],
+            planets: vec![],
+            ships: vec![],
+            starts: vec![],
+            wormholes: es_data_parser::wormhole_links(es_game_data)
                 .iter()
-                .map(|object| Object {
-                    sprite: object.sprite.map(String::from),
-                    distance: object.distance.unwrap_or(0.0),
-                    period: object.period,
-                })
+                .map(|link| (Symbol::intern(link.from), Symbol::intern(link.to)))
                 .collect(),
-        })
-        .collect::<Vec<_>>();
-    Game {
+        };
+        for object in es_game_data {
+            match object {
+                es_data_parser::Object::Ship(ship) => file.ships.push(Arc::new(Ship {
+                    name: Symbol::intern(ship.name),
+                    sprite: Sprite::from(&ship.sprite),
+                    fuel_capacity: ship.attributes.fuel_capacity,
+                    outfits: ship
+                        .outfits
+                        .iter()
+                        .map(|(outfit, count)| (Symbol::intern(outfit), *count))
+                        .collect(),
+                })),
+                es_data_parser::Object::System(system) => file.systems.push(System {
+                    name: Symbol::intern(system.name),
+                    pos: system.pos,
+                    government: Symbol::intern(system.government),
+                    links: system
+                        .links
+                        .iter()
+                        .map(|link| Symbol::intern(link))
+                        .collect(),
+                    jump_range: system.jump_range,
+                    ramscoop: system.ramscoop,
+                    objects: system.objects.iter().map(Object::from).collect(),
+                }),
+                es_data_parser::Object::Planet(planet) => file.planets.push(Planet {
+                    name: Symbol::intern(planet.name),
+                    government: planet.government.map(Symbol::intern),
+                    landscape: planet.landscape.map(String::from),
+                    description: planet
+                        .description
+                        .iter()
+                        .map(|s| String::from(*s))
+                        .collect(),
+                    spaceport: planet.spaceport.iter().map(|s| String::from(*s)).collect(),
+                }),
+                es_data_parser::Object::Start(start) => file.starts.push(Start {
+                    name: Symbol::intern(start.name.unwrap_or_default()),
+                    date: start.date,
+                    system: Symbol::intern(start.system),
+                    planet: Symbol::intern(start.planet),
+                    account: start.account,
+                    set: String::from(start.set),
+                    ships: start
+                        .ships
+                        .iter()
+                        .map(|ship| (Symbol::intern(ship.model), ship.name.map(String::from)))
+                        .collect(),
+                }),
+                _ => (),
+            }
+        }
+        file
+    }
+}
+
+impl Game {
+    /// Rebuild the universe from the data files, later files overriding earlier ones
+    pub(crate) fn merge_files(&mut self) {
+        self.systems = self
+            .files
+            .iter()
+            .flat_map(|file| file.systems.iter().cloned())
+            .collect();
+        self.planets = self
+            .files
+            .iter()
+            .flat_map(|file| file.planets.iter().cloned())
+            .collect();
+        self.ships = self
+            .files
+            .iter()
+            .flat_map(|file| file.ships.iter().cloned())
+            .collect();
+        self.starts = self
+            .files
+            .iter()
+            .flat_map(|file| file.starts.iter().cloned())
+            .collect();
+        self.wormholes = self
+            .files
+            .iter()
+            .flat_map(|file| file.wormholes.iter().copied())
+            .collect();
+    }
+}
+
+pub(crate) fn from_data_files(files: Vec<DataFile>) -> Result<Game, GameDataError> {
+    let mut game = Game {
         player: Player {
-            ship: ships
-                .iter()
-                .find(|ship| ship.name == "Shuttle")
-                .unwrap()
-                .clone(),
+            first_name: String::new(),
+            last_name: String::new(),
+            date: Date {
+                day: 1,
+                month: 1,
+                year: 3013,
+            },
+            system: Symbol::default(),
+            planet: None,
+            account: Account {
+                credits: 0,
+                score: 0,
+                mortgage: None,
+            },
+            debts: vec![],
+            fleet: vec![],
+            visited: vec![],
+            conditions: vec![],
         },
-        systems,
-        ships,
-    }
+        systems: Registry::new(),
+        planets: Registry::new(),
+        ships: Registry::new(),
+        starts: Registry::new(),
+        wormholes: vec![],
+        files,
+    };
+    game.merge_files();
+
+    let start = game
+        .starts
+        .first()
+        .map(|start| start.name)
+        .ok_or(GameDataError::NoStart(None))?;
+    game.begin(start)?;
+    Ok(game)
 }
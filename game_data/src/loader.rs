@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use es_data_parser::data_files;
+
+use crate::error::io_error;
+use crate::{from_data_files, DataFile, Game, GameDataError};
+
+/// Start a game from all the `.txt` files of a data directory
+///
+/// Files are read in path order, and a definition in a later file overrides an earlier one with
+/// the same name, whether files are parsed in parallel or not.
+pub fn start_from_es_dir(path: impl AsRef<Path>) -> Result<Game, GameDataError> {
+    let files = data_files(path, |path, error| io_error(path)(error))?;
+    let sources = files
+        .into_iter()
+        .map(|file| {
+            let source = fs::read_to_string(&file).map_err(io_error(&file))?;
+            Ok((file, source))
+        })
+        .collect::<Result<Vec<_>, GameDataError>>()?;
+
+    // the error of the first file that fails is reported, whether files are parsed in parallel
+    // or not
+    from_data_files(
+        parse_sources(&sources)
+            .into_iter()
+            .collect::<Result<_, _>>()?,
+    )
+}
+
+#[cfg(not(feature = "rayon"))]
+fn parse_sources(sources: &[(PathBuf, String)]) -> Vec<Result<DataFile, GameDataError>> {
+    sources
+        .iter()
+        .map(|(path, source)| DataFile::parse(path, source))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+fn parse_sources(sources: &[(PathBuf, String)]) -> Vec<Result<DataFile, GameDataError>> {
+    // collecting an indexed parallel iterator keeps the files order
+    sources
+        .par_iter()
+        .map(|(path, source)| DataFile::parse(path, source))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::start_from_es_dir;
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    #[test]
+    fn later_files_override_earlier_ones() {
+        let dir = std::env::temp_dir().join(format!("game_data_loader_{}", std::process::id()));
+        fs::create_dir_all(dir.join("ships")).unwrap();
+        fs::write(dir.join("game.txt"), ES_DATA).unwrap();
+        let shuttle = &ES_DATA[ES_DATA.find("\nship \"Shuttle\"").unwrap() + 1..];
+        fs::write(
+            dir.join("ships").join("shuttle.txt"),
+            shuttle.replace("ship/shuttle", "ship/shuttle-refit"),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.md"), "not data").unwrap();
+
+        let game = start_from_es_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(game.systems.len(), 1);
+        assert_eq!(game.ships.len(), 1);
+        assert_eq!(game.ships[0].sprite.name, "ship/shuttle-refit");
+    }
+}
@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::{Position, Registry, Symbol, System};
+
+/// Range of a jump drive in a system that doesn't set its own
+pub const DEFAULT_JUMP_RANGE: f64 = 100.;
+
+/// How a ship travels between systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drive {
+    /// only along hyperspace links
+    Hyperdrive,
+    /// along hyperspace links, and to any system in the jump range
+    JumpDrive,
+}
+
+/// Systems and the jumps between them
+#[derive(Debug, Clone)]
+pub struct GalaxyMap {
+    systems: Vec<Symbol>,
+    index: HashMap<Symbol, usize>,
+    positions: Vec<Position>,
+    governments: Vec<Symbol>,
+    jumps: Vec<Vec<usize>>,
+    /// jumps along hyperspace links
+    links: Vec<Vec<usize>>,
+}
+
+impl GalaxyMap {
+    /// Map of the jumps possible with a drive. Links go both ways, even when only one of the
+    /// systems declares them, and links to unknown systems are ignored. Wormholes, given from
+    /// the system where they are entered to the one they lead to, are one way links.
+    pub fn new(
+        systems: &Registry<System>,
+        wormholes: &[(Symbol, Symbol)],
+        drive: Drive,
+    ) -> GalaxyMap {
+        let index = systems
+            .iter()
+            .enumerate()
+            .map(|(position, system)| (system.name, position))
+            .collect::<HashMap<_, _>>();
+        let positions = systems.iter().map(|system| system.pos).collect::<Vec<_>>();
+
+        let mut jumps = vec![vec![]; systems.len()];
+        let mut links = vec![vec![]; systems.len()];
+        let add = |jumps: &mut Vec<Vec<usize>>, from: usize, to: usize| {
+            if from != to && !jumps[from].contains(&to) {
+                jumps[from].push(to);
+            }
+        };
+        for (from, system) in systems.iter().enumerate() {
+            for to in system.links.iter().filter_map(|link| index.get(link)) {
+                for (from, to) in [(from, *to), (*to, from)] {
+                    add(&mut jumps, from, to);
+                    add(&mut links, from, to);
+                }
+            }
+            if drive == Drive::JumpDrive {
+                let range = system.jump_range.map_or(DEFAULT_JUMP_RANGE, f64::from);
+                for to in 0..positions.len() {
+                    if distance(positions[from], positions[to]) <= range {
+                        add(&mut jumps, from, to);
+                    }
+                }
+            }
+        }
+        for (from, to) in wormholes {
+            if let (Some(&from), Some(&to)) = (index.get(from), index.get(to)) {
+                add(&mut jumps, from, to);
+                add(&mut links, from, to);
+            }
+        }
+
+        GalaxyMap {
+            systems: systems.iter().map(|system| system.name).collect(),
+            index,
+            positions,
+            governments: systems.iter().map(|system| system.government).collect(),
+            jumps,
+            links,
+        }
+    }
+
+    /// Systems one jump away
+    pub fn neighbours(&self, system: Symbol) -> impl Iterator<Item = Symbol> + '_ {
+        self.index
+            .get(&system)
+            .into_iter()
+            .flat_map(move |from| self.jumps[*from].iter())
+            .map(move |to| self.systems[*to])
+    }
+
+    /// Route with the fewest jumps, from `from` to `to` both included
+    ///
+    /// Between routes with as many jumps, the one with the fewest jumps outside of hyperspace
+    /// links is chosen, as they use more fuel.
+    pub fn route(&self, from: Symbol, to: Symbol) -> Option<Vec<Symbol>> {
+        let (from, to) = (*self.index.get(&from)?, *self.index.get(&to)?);
+        let mut previous = vec![None; self.systems.len()];
+        // jumps and jumps outside of links to get to each system
+        let mut costs = vec![(usize::MAX, usize::MAX); self.systems.len()];
+        let mut queue = VecDeque::new();
+        previous[from] = Some(from);
+        costs[from] = (0, 0);
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                return Some(self.walk_back(&previous, to));
+            }
+            // systems are visited by number of jumps, so the cost of this one is final
+            let (jumps, outside_links) = costs[current];
+            for next in self.jumps[current].iter().copied() {
+                let outside_links =
+                    outside_links + usize::from(!self.links[current].contains(&next));
+                let cost = (jumps + 1, outside_links);
+                if cost < costs[next] {
+                    if previous[next].is_none() {
+                        queue.push_back(next);
+                    }
+                    costs[next] = cost;
+                    previous[next] = Some(current);
+                }
+            }
+        }
+        None
+    }
+
+    /// Shortest route in distance travelled, never going through a system of a hostile
+    /// government, though the destination can be one
+    pub fn route_avoiding(
+        &self,
+        from: Symbol,
+        to: Symbol,
+        hostile: &[Symbol],
+    ) -> Option<Vec<Symbol>> {
+        let (from, to) = (*self.index.get(&from)?, *self.index.get(&to)?);
+        let mut costs = vec![f64::INFINITY; self.systems.len()];
+        let mut previous = vec![None; self.systems.len()];
+        let mut queue = BinaryHeap::new();
+        costs[from] = 0.;
+        previous[from] = Some(from);
+        queue.push(Step {
+            cost: 0.,
+            system: from,
+        });
+        while let Some(Step { cost, system }) = queue.pop() {
+            if system == to {
+                return Some(self.walk_back(&previous, to));
+            }
+            if cost > costs[system] {
+                continue;
+            }
+            for next in self.jumps[system].iter().copied() {
+                if next != to && hostile.contains(&self.governments[next]) {
+                    continue;
+                }
+                let cost = cost + distance(self.positions[system], self.positions[next]);
+                if cost < costs[next] {
+                    costs[next] = cost;
+                    previous[next] = Some(system);
+                    queue.push(Step { cost, system: next });
+                }
+            }
+        }
+        None
+    }
+
+    /// All the systems that can be reached from a system, including itself
+    pub fn reachable(&self, from: Symbol) -> Vec<Symbol> {
+        let from = match self.index.get(&from) {
+            Some(from) => *from,
+            None => return vec![],
+        };
+        let mut seen = vec![false; self.systems.len()];
+        let mut queue = VecDeque::new();
+        let mut reachable = vec![];
+        seen[from] = true;
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            reachable.push(self.systems[current]);
+            for next in &self.jumps[current] {
+                if !seen[*next] {
+                    seen[*next] = true;
+                    queue.push_back(*next);
+                }
+            }
+        }
+        reachable
+    }
+
+    pub fn is_reachable(&self, from: Symbol, to: Symbol) -> bool {
+        self.route(from, to).is_some()
+    }
+
+    fn walk_back(&self, previous: &[Option<usize>], to: usize) -> Vec<Symbol> {
+        let mut route = vec![self.systems[to]];
+        let mut current = to;
+        while let Some(before) = previous[current].filter(|before| *before != current) {
+            route.push(self.systems[before]);
+            current = before;
+        }
+        route.reverse();
+        route
+    }
+}
+
+fn distance(from: Position, to: Position) -> f64 {
+    (to.x - from.x).hypot(to.y - from.y)
+}
+
+/// A system to visit in a weighted search, the cheapest first
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Step {
+    cost: f64,
+    system: usize,
+}
+
+impl Eq for Step {}
+
+impl Ord for Step {
+    fn cmp(&self, other: &Step) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.system.cmp(&other.system))
+    }
+}
+
+impl PartialOrd for Step {
+    fn partial_cmp(&self, other: &Step) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DataFile, Drive, GalaxyMap, Registry, Symbol, System};
+
+    // Sol - Alpha - Beta - Delta, with a shortcut Sol - Pirate - Delta through pirate space,
+    // Lonely out of reach of links but close to Delta, and Alpha with a longer jump range
+    const GALAXY: &str = r#"system Sol
+	pos 0 0
+	government Republic
+	habitable 1
+	link Alpha
+	link Pirate
+system Alpha
+	pos 100 0
+	government Republic
+	habitable 1
+	"jump range" 150
+	link Beta
+system Beta
+	pos 200 0
+	government Republic
+	habitable 1
+	link Delta
+system Delta
+	pos 200 100
+	government Republic
+	habitable 1
+system Pirate
+	pos 100 100
+	government Pirate
+	habitable 1
+	link Delta
+system Lonely
+	pos 250 150
+	government Republic
+	habitable 1
+"#;
+
+    fn systems() -> Registry<System> {
+        DataFile::from_es_objects("", &es_data_parser::parse(GALAXY))
+            .systems
+            .into_iter()
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<Symbol> {
+        names.iter().map(|name| Symbol::intern(name)).collect()
+    }
+
+    #[test]
+    fn can_route_through_links() {
+        let systems = systems();
+        assert_eq!(systems.len(), 6);
+        let map = GalaxyMap::new(&systems, &[], Drive::Hyperdrive);
+
+        assert_eq!(
+            map.neighbours(Symbol::intern("Delta")).collect::<Vec<_>>(),
+            names(&["Beta", "Pirate"])
+        );
+        assert_eq!(
+            map.route("Sol".into(), "Delta".into()),
+            Some(names(&["Sol", "Pirate", "Delta"]))
+        );
+        assert_eq!(
+            map.route_avoiding("Sol".into(), "Delta".into(), &names(&["Pirate"])),
+            Some(names(&["Sol", "Alpha", "Beta", "Delta"]))
+        );
+        assert_eq!(
+            map.route_avoiding("Sol".into(), "Pirate".into(), &names(&["Pirate"])),
+            Some(names(&["Sol", "Pirate"]))
+        );
+        assert_eq!(map.route("Sol".into(), "Sol".into()), Some(names(&["Sol"])));
+        assert_eq!(map.route("Sol".into(), "Lonely".into()), None);
+        assert!(!map.is_reachable("Sol".into(), "Lonely".into()));
+        assert_eq!(map.reachable("Lonely".into()), names(&["Lonely"]));
+        assert_eq!(map.reachable("Sol".into()).len(), 5);
+    }
+
+    #[test]
+    fn can_route_with_jump_drive() {
+        let map = GalaxyMap::new(&systems(), &[], Drive::JumpDrive);
+
+        // through Pirate rather than Alpha, jumping outside of links only once
+        assert_eq!(
+            map.route("Sol".into(), "Lonely".into()),
+            Some(names(&["Sol", "Pirate", "Delta", "Lonely"]))
+        );
+        assert_eq!(
+            map.route("Alpha".into(), "Delta".into()),
+            Some(names(&["Alpha", "Delta"]))
+        );
+        assert_eq!(
+            map.route_avoiding("Sol".into(), "Delta".into(), &names(&["Pirate"])),
+            Some(names(&["Sol", "Alpha", "Delta"]))
+        );
+    }
+
+    #[test]
+    fn can_route_through_wormholes() {
+        let wormholes = [
+            (Symbol::intern("Delta"), Symbol::intern("Lonely")),
+            (Symbol::intern("Sol"), Symbol::intern("Nowhere")),
+        ];
+        let map = GalaxyMap::new(&systems(), &wormholes, Drive::Hyperdrive);
+
+        assert_eq!(
+            map.route("Sol".into(), "Lonely".into()),
+            Some(names(&["Sol", "Pirate", "Delta", "Lonely"]))
+        );
+        // wormholes only lead one way
+        assert_eq!(map.route("Lonely".into(), "Delta".into()), None);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Date, Object, Position, System};
+
+/// Number of days from the 1st of January of year 1 to a date
+pub fn days_since_epoch(date: &Date) -> f64 {
+    days_from_civil(
+        i64::from(date.year),
+        i64::from(date.month),
+        i64::from(date.day),
+    ) as f64
+        - days_from_civil(1, 1, 1) as f64
+}
+
+/// Days from the 1st of January 1970, in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+impl System {
+    /// Position of each object, moons included, after the given number of days since the epoch
+    ///
+    /// Objects orbit their parent, or the center of the system, once every `period` days,
+    /// starting `offset` degrees clockwise from straight up. Parents come before their children.
+    pub fn positions_at(&self, days: f64) -> Vec<(&Object, Position)> {
+        let mut positions = vec![];
+        for object in &self.objects {
+            add_positions(object, days, Position { x: 0., y: 0. }, &mut positions);
+        }
+        positions
+    }
+}
+
+fn add_positions<'a>(
+    object: &'a Object,
+    days: f64,
+    center: Position,
+    positions: &mut Vec<(&'a Object, Position)>,
+) {
+    let turns = if object.period != 0. {
+        days / f64::from(object.period)
+    } else {
+        0.
+    };
+    let angle = (turns * 360. + f64::from(object.offset))
+        .rem_euclid(360.)
+        .to_radians();
+    let distance = f64::from(object.distance);
+    let position = Position {
+        x: center.x + angle.sin() * distance,
+        y: center.y - angle.cos() * distance,
+    };
+    positions.push((object, position));
+    for child in &object.children {
+        add_positions(child, days, position, positions);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{days_since_epoch, start_from_es_data, Date, Object, Position, Symbol, System};
+
+    fn assert_close(position: Position, x: f64, y: f64) {
+        assert!(
+            (position.x - x).abs() < 1e-6 && (position.y - y).abs() < 1e-6,
+            "{:?} is not ({}, {})",
+            position,
+            x,
+            y
+        );
+    }
+
+    #[test]
+    fn can_count_days() {
+        let date = |day, month, year| Date { day, month, year };
+        assert_eq!(days_since_epoch(&date(1, 1, 1)), 0.);
+        assert_eq!(days_since_epoch(&date(1, 1, 2)), 365.);
+        assert_eq!(
+            days_since_epoch(&date(1, 3, 3012)) - days_since_epoch(&date(28, 2, 3012)),
+            2.
+        );
+        assert_eq!(
+            days_since_epoch(&date(1, 3, 3100)) - days_since_epoch(&date(28, 2, 3100)),
+            1.
+        );
+    }
+
+    #[test]
+    fn can_compute_orbits() {
+        let system = System {
+            name: Symbol::intern("Sol"),
+            pos: Position { x: 0., y: 0. },
+            government: Symbol::intern("Republic"),
+            links: vec![],
+            jump_range: None,
+            ramscoop: None,
+            objects: vec![
+                Object {
+                    period: 10.,
+                    ..Object::default()
+                },
+                Object {
+                    distance: 100.,
+                    period: 10.,
+                    offset: 90.,
+                    children: vec![Object {
+                        distance: 10.,
+                        period: 1.,
+                        ..Object::default()
+                    }],
+                    ..Object::default()
+                },
+            ],
+        };
+
+        let positions = system.positions_at(0.);
+        assert_eq!(positions.len(), 3);
+        assert_close(positions[0].1, 0., 0.);
+        assert_close(positions[1].1, 100., 0.);
+        assert_close(positions[2].1, 100., -10.);
+
+        let positions = system.positions_at(2.5);
+        assert_eq!(positions[1].0.offset, 90.);
+        assert_close(positions[1].1, 0., 100.);
+        assert_close(positions[2].1, 0., 110.);
+    }
+
+    #[test]
+    fn can_place_objects_of_a_system() {
+        let game = start_from_es_data(include_str!("../../data/simple_game.txt")).unwrap();
+        let system = game.systems.get(game.player.system).unwrap();
+        let positions = system.positions_at(days_since_epoch(&game.player.date));
+
+        assert_eq!(positions.len(), 9);
+        for object in &system.objects {
+            let (_, position) = positions
+                .iter()
+                .find(|(placed, _)| std::ptr::eq(*placed, object))
+                .unwrap();
+            assert!((position.x.hypot(position.y) - f64::from(object.distance)).abs() < 1e-2);
+        }
+        // a moon stays at its distance from its planet
+        let (planet, moon) = (positions[4].1, positions[5].1);
+        assert!(((moon.x - planet.x).hypot(moon.y - planet.y) - 249.).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::{Planet, Ship, Start, Symbol, System};
+
+/// Something defined by its name in the data files
+pub trait Named {
+    fn name(&self) -> Symbol;
+}
+
+impl Named for System {
+    fn name(&self) -> Symbol {
+        self.name
+    }
+}
+
+impl Named for Planet {
+    fn name(&self) -> Symbol {
+        self.name
+    }
+}
+
+impl Named for Ship {
+    fn name(&self) -> Symbol {
+        self.name
+    }
+}
+
+impl Named for Start {
+    fn name(&self) -> Symbol {
+        self.name
+    }
+}
+
+impl<T: Named> Named for Arc<T> {
+    fn name(&self) -> Symbol {
+        (**self).name()
+    }
+}
+
+/// Definitions of one kind, in order, indexed by name
+///
+/// It can be used as a slice, and looked up by name without scanning it.
+#[derive(Clone, PartialEq)]
+pub struct Registry<T> {
+    items: Vec<T>,
+    index: HashMap<Symbol, usize>,
+}
+
+impl<T: Named> Registry<T> {
+    pub fn new() -> Registry<T> {
+        Registry {
+            items: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Add a definition. One with the same name is replaced, keeping its position.
+    pub fn insert(&mut self, item: T) {
+        match self.index.get(&item.name()) {
+            Some(&position) => self.items[position] = item,
+            None => {
+                self.index.insert(item.name(), self.items.len());
+                self.items.push(item);
+            }
+        }
+    }
+
+    pub fn get(&self, name: Symbol) -> Option<&T> {
+        self.index.get(&name).map(|&position| &self.items[position])
+    }
+
+    /// Look up a definition by a name that may never have been interned
+    pub fn get_by_name(&self, name: &str) -> Option<&T> {
+        Symbol::get(name).and_then(|name| self.get(name))
+    }
+}
+
+impl<T: Named> Default for Registry<T> {
+    fn default() -> Registry<T> {
+        Registry::new()
+    }
+}
+
+/// Later definitions override earlier ones with the same name
+impl<T: Named> FromIterator<T> for Registry<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Registry<T> {
+        let mut registry = Registry::new();
+        for item in items {
+            registry.insert(item);
+        }
+        registry
+    }
+}
+
+impl<T> Deref for Registry<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Registry<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.items).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Position, Registry, Symbol, System};
+
+    fn system(name: &str, objects: usize) -> System {
+        System {
+            name: Symbol::intern(name),
+            pos: Position { x: 0., y: 0. },
+            government: Symbol::intern("Republic"),
+            links: vec![],
+            jump_range: None,
+            ramscoop: None,
+            objects: vec![Default::default(); objects],
+        }
+    }
+
+    #[test]
+    fn later_definitions_override_earlier_ones() {
+        let registry = vec![system("Sol", 1), system("Vega", 0), system("Sol", 2)]
+            .into_iter()
+            .collect::<Registry<_>>();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry[0].name, "Sol");
+        assert_eq!(registry[0].objects.len(), 2);
+        assert_eq!(registry.get(Symbol::intern("Vega")), Some(&registry[1]));
+        assert_eq!(registry.get_by_name("Sol").unwrap().objects.len(), 2);
+        assert_eq!(registry.get_by_name("Unknown"), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use crate::{DataFile, Game, GameDataError, Named, Registry, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataKind {
+    System,
+    Planet,
+    Ship,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(DataKind, Symbol),
+    Removed(DataKind, Symbol),
+    Modified(DataKind, Symbol),
+}
+
+impl Game {
+    /// Replace the content of a data file, and update the universe with it
+    ///
+    /// Only this file is parsed again. A file not loaded yet is added, and empty contents remove
+    /// everything the file defined. Returns what changed in the universe, or the diagnostics of
+    /// contents that can't be parsed, keeping what the file defined before.
+    pub fn reload(
+        &mut self,
+        path: impl AsRef<Path>,
+        contents: &str,
+    ) -> Result<Vec<Change>, GameDataError> {
+        let path = path.as_ref();
+        let file = DataFile::parse(path, contents)?;
+        match self.files.iter().position(|existing| existing.path == path) {
+            Some(index) if self.files[index] == file => return Ok(vec![]),
+            Some(index) => self.files[index] = file,
+            None => {
+                let index = self
+                    .files
+                    .iter()
+                    .position(|existing| existing.path.as_path() > path)
+                    .unwrap_or(self.files.len());
+                self.files.insert(index, file);
+            }
+        }
+
+        let systems = std::mem::take(&mut self.systems);
+        let planets = std::mem::take(&mut self.planets);
+        let ships = std::mem::take(&mut self.ships);
+        self.merge_files();
+
+        let mut changes = diff(DataKind::System, &systems, &self.systems);
+        changes.extend(diff(DataKind::Planet, &planets, &self.planets));
+        changes.extend(diff(DataKind::Ship, &ships, &self.ships));
+
+        for ship in &mut self.player.fleet {
+            if let Some(model) = self.ships.get(ship.model.name) {
+                ship.model = model.clone();
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+fn diff<T: Named + PartialEq>(kind: DataKind, old: &Registry<T>, new: &Registry<T>) -> Vec<Change> {
+    let mut changes = vec![];
+    for item in old {
+        if new.get(item.name()).is_none() {
+            changes.push(Change::Removed(kind, item.name()));
+        }
+    }
+    for item in new {
+        match old.get(item.name()) {
+            None => changes.push(Change::Added(kind, item.name())),
+            Some(other) if other != item => changes.push(Change::Modified(kind, item.name())),
+            Some(_) => (),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{start_from_es_data, Change, DataKind, GameDataError, Symbol};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    #[test]
+    fn can_reload_a_file() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        assert_eq!(game.reload("", ES_DATA).unwrap(), vec![]);
+
+        let changes = game
+            .reload("", &ES_DATA.replace("ship/shuttle", "ship/shuttle-refit"))
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::Modified(DataKind::Ship, Symbol::intern("Shuttle"))]
+        );
+        assert_eq!(
+            game.player.flagship().unwrap().model.sprite.name,
+            "ship/shuttle-refit"
+        );
+    }
+
+    #[test]
+    fn can_add_and_remove_a_file() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        let planet = "planet Mars\n\tdescription `Red`\n";
+
+        let changes = game.reload("mars.txt", planet).unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::Added(DataKind::Planet, Symbol::intern("Mars"))]
+        );
+        assert_eq!(game.planets.len(), 2);
+
+        let changes = game.reload("mars.txt", "").unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::Removed(DataKind::Planet, Symbol::intern("Mars"))]
+        );
+    }
+
+    #[test]
+    fn will_keep_definitions_on_syntax_error() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        let (systems, planets, ships) = (
+            game.systems.clone(),
+            game.planets.clone(),
+            game.ships.clone(),
+        );
+
+        let typo = ES_DATA.replace("pos -535 273", "pos -535 2 73");
+        match game.reload("", &typo) {
+            Err(GameDataError::Parse { diagnostics, .. }) => assert_eq!(diagnostics.len(), 1),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(game.systems, systems);
+        assert_eq!(game.planets, planets);
+        assert_eq!(game.ships, ships);
+        assert_eq!(game.reload("", ES_DATA).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn will_wait_for_a_half_typed_file() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        let planet = "planet Mars\n\tdescription `Red`\n";
+        game.reload("mars.txt", planet).unwrap();
+
+        let typing = format!("{}planet Phobos\n\tdescription `Gr", planet);
+        assert!(game.reload("mars.txt", &typing).is_err());
+        assert_eq!(game.planets.len(), 2);
+        assert!(game.planets.get_by_name("Mars").is_some());
+
+        let typed = format!("{}planet Phobos\n\tdescription `Grey`\n", planet);
+        assert_eq!(
+            game.reload("mars.txt", &typed).unwrap(),
+            vec![Change::Added(DataKind::Planet, Symbol::intern("Phobos"))]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::Write;
+
+use es_data_parser::Token;
+
+use crate::{DataKind, Game, GameDataError, Player, PlayerShip, Symbol};
+
+impl Game {
+    /// Save the player, in the same format as Endless Sky pilot files
+    ///
+    /// Fails if a name can't be written so that it's read back as is.
+    pub fn save(&self) -> Result<String, GameDataError> {
+        let player = &self.player;
+        let mut save = String::new();
+
+        writeln!(
+            save,
+            "pilot {} {}",
+            token(&player.first_name)?,
+            token(&player.last_name)?
+        )
+        .unwrap();
+        writeln!(
+            save,
+            "date {} {} {}",
+            player.date.day, player.date.month, player.date.year
+        )
+        .unwrap();
+        writeln!(save, "system {}", token(&player.system)?).unwrap();
+        if let Some(planet) = &player.planet {
+            writeln!(save, "planet {}", token(planet)?).unwrap();
+        }
+
+        writeln!(save, "account").unwrap();
+        writeln!(save, "\tcredits {}", player.account.credits).unwrap();
+        writeln!(save, "\tscore {}", player.account.score).unwrap();
+        let debts = player
+            .account
+            .mortgage
+            .iter()
+            .map(|mortgage| ("Mortgage", mortgage))
+            .chain(
+                player
+                    .debts
+                    .iter()
+                    .map(|(kind, debt)| (kind.as_str(), debt)),
+            );
+        for (kind, mortgage) in debts {
+            writeln!(save, "\tmortgage {}", token(kind)?).unwrap();
+            writeln!(save, "\t\tprincipal {}", mortgage.principal).unwrap();
+            writeln!(save, "\t\tinterest {}", mortgage.interest).unwrap();
+            writeln!(save, "\t\tterm {}", mortgage.term).unwrap();
+        }
+
+        for ship in &player.fleet {
+            writeln!(save, "ship {}", token(&ship.model.name)?).unwrap();
+            writeln!(save, "\tname {}", token(&ship.name)?).unwrap();
+            writeln!(save, "\tsprite {}", token(&ship.model.sprite.name)?).unwrap();
+            writeln!(save, "\toutfits").unwrap();
+            for (outfit, count) in &ship.outfits {
+                if *count == 1 {
+                    writeln!(save, "\t\t{}", token(outfit)?).unwrap();
+                } else {
+                    writeln!(save, "\t\t{} {}", token(outfit)?, count).unwrap();
+                }
+            }
+            if let Some(system) = &ship.system {
+                writeln!(save, "\tsystem {}", token(system)?).unwrap();
+            }
+            if let Some(planet) = &ship.planet {
+                writeln!(save, "\tplanet {}", token(planet)?).unwrap();
+            }
+            if ship.parked {
+                writeln!(save, "\tparked").unwrap();
+            }
+        }
+
+        for system in &player.visited {
+            writeln!(save, "visited {}", token(system)?).unwrap();
+        }
+
+        if !player.conditions.is_empty() {
+            writeln!(save, "conditions").unwrap();
+            for (condition, value) in &player.conditions {
+                if *value == 1 {
+                    writeln!(save, "\t{}", token(condition)?).unwrap();
+                } else {
+                    writeln!(save, "\t{} {}", token(condition)?, value).unwrap();
+                }
+            }
+        }
+
+        Ok(save)
+    }
+
+    /// Restore the player from a save, replacing the current one. Ships are looked up in the
+    /// ships known by the game, and nothing is changed if the save is invalid.
+    pub fn load(&mut self, save: &str) -> Result<(), GameDataError> {
+        let save = es_data_parser::read_save_game(save).map_err(GameDataError::InvalidSave)?;
+
+        let fleet = save
+            .ships
+            .iter()
+            .map(|saved_ship| {
+                let model = self.ships.get_by_name(saved_ship.model).ok_or_else(|| {
+                    GameDataError::MissingReference {
+                        kind: DataKind::Ship,
+                        name: Symbol::intern(saved_ship.model),
+                    }
+                })?;
+                Ok(PlayerShip {
+                    name: String::from(saved_ship.name.unwrap_or(saved_ship.model)),
+                    model: model.clone(),
+                    outfits: saved_ship
+                        .outfits
+                        .iter()
+                        .map(|(outfit, count)| (Symbol::intern(outfit), *count))
+                        .collect(),
+                    system: saved_ship.system.map(Symbol::intern),
+                    planet: saved_ship.planet.map(Symbol::intern),
+                    parked: saved_ship.parked,
+                })
+            })
+            .collect::<Result<Vec<_>, GameDataError>>()?;
+
+        self.player = Player {
+            first_name: String::from(save.first_name),
+            last_name: String::from(save.last_name),
+            date: save.date,
+            system: Symbol::intern(save.system),
+            planet: save.planet.map(Symbol::intern),
+            account: save.account,
+            debts: save
+                .debts
+                .iter()
+                .map(|(kind, debt)| (String::from(*kind), *debt))
+                .collect(),
+            fleet,
+            visited: save
+                .visited
+                .iter()
+                .map(|system| Symbol::intern(system))
+                .collect(),
+            conditions: save
+                .conditions
+                .iter()
+                .map(|(condition, value)| (String::from(*condition), *value))
+                .collect(),
+        };
+        Ok(())
+    }
+}
+
+/// quote a token so that it's read back as is
+fn token(value: &str) -> Result<Token<'_>, GameDataError> {
+    Token::new(value).ok_or_else(|| GameDataError::Unwritable(String::from(value)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{start_from_es_data, Account, DataKind, Date, GameDataError, Mortgage, Symbol};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+    const PILOT: &str = include_str!("../fixtures/pilot.txt");
+
+    #[test]
+    fn can_load_pilot() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        game.load(PILOT).unwrap();
+
+        let player = &game.player;
+        assert_eq!(player.first_name, "Bob");
+        assert_eq!(player.last_name, "Smith");
+        assert_eq!(
+            player.date,
+            Date {
+                day: 20,
+                month: 11,
+                year: 3013
+            }
+        );
+        assert_eq!(player.system, "Rutilicus");
+        assert_eq!(player.planet, Some(Symbol::intern("New Boston")));
+        assert_eq!(
+            player.account,
+            Account {
+                credits: 131000,
+                score: 400,
+                mortgage: Some(Mortgage {
+                    principal: 480000,
+                    interest: 0.004,
+                    term: 365
+                })
+            }
+        );
+        assert_eq!(player.fleet.len(), 2);
+        assert_eq!(player.flagship().unwrap().name, "Bad Sheep");
+        assert_eq!(player.flagship().unwrap().model.name, "Shuttle");
+        assert_eq!(
+            player.flagship().unwrap().outfits.last(),
+            Some(&(Symbol::intern("Meteor Missile Launcher"), 2))
+        );
+        assert_eq!(
+            player.debts,
+            vec![(
+                String::from("Fine"),
+                Mortgage {
+                    principal: 5000,
+                    interest: 0.01,
+                    term: 60
+                }
+            )]
+        );
+        assert_eq!(player.fleet[0].system, Some(Symbol::intern("Rutilicus")));
+        assert!(!player.fleet[0].parked);
+        assert_eq!(player.fleet[1].name, "Bob's \"Spare\"");
+        assert_eq!(player.fleet[1].system, Some(Symbol::intern("Arcturus")));
+        assert_eq!(player.fleet[1].planet, None);
+        assert!(player.fleet[1].parked);
+        assert_eq!(player.visited, vec!["Rutilicus", "Arcturus"]);
+        assert_eq!(
+            player.conditions,
+            vec![
+                (String::from("license: Pilot's"), 1),
+                (String::from("day"), 20)
+            ]
+        );
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        game.load(PILOT).unwrap();
+        let saved = game.save().unwrap();
+
+        let mut reloaded = start_from_es_data(ES_DATA).unwrap();
+        reloaded.load(&saved).unwrap();
+        assert_eq!(reloaded.player, game.player);
+        assert_eq!(reloaded.save().unwrap(), saved);
+    }
+
+    #[test]
+    fn wont_save_unwritable_names() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        game.player.fleet[0].name = String::from("Bob's \"`Spare`\"");
+        assert!(matches!(
+            game.save(),
+            Err(GameDataError::Unwritable(name)) if name == "Bob's \"`Spare`\""
+        ));
+    }
+
+    #[test]
+    fn wont_load_unknown_ships() {
+        let mut game = start_from_es_data(ES_DATA).unwrap();
+        let save = PILOT.replace("ship Shuttle", "ship Unknown");
+        assert!(matches!(
+            game.load(&save),
+            Err(GameDataError::MissingReference {
+                kind: DataKind::Ship,
+                name
+            }) if name == "Unknown"
+        ));
+        let error = game.load("pilot Bob Smith\n\tdate soon\n").unwrap_err();
+        assert!(matches!(error, GameDataError::InvalidSave(_)));
+        assert!(error
+            .to_string()
+            .starts_with("could not parse the save game\n"));
+        assert_eq!(game.player.first_name, "");
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{DataKind, Game, GameDataError, PlayerShip, Symbol};
+
+impl Game {
+    /// Begin a new game from the start scenario with this name, the empty name standing for the
+    /// start without name
+    ///
+    /// The player gets the date, location, account, condition and ships of the scenario. Nothing
+    /// is changed if there is no such start, or if it references something unknown.
+    pub fn begin(&mut self, start: Symbol) -> Result<(), GameDataError> {
+        let start = self
+            .starts
+            .get(start)
+            .ok_or(GameDataError::NoStart(Some(start)))?;
+        let missing = |kind, name| GameDataError::MissingReference { kind, name };
+        if self.systems.get(start.system).is_none() {
+            return Err(missing(DataKind::System, start.system));
+        }
+        if self.planets.get(start.planet).is_none() {
+            return Err(missing(DataKind::Planet, start.planet));
+        }
+        let fleet = start
+            .ships
+            .iter()
+            .map(|(model, name)| {
+                let model = self
+                    .ships
+                    .get(*model)
+                    .ok_or_else(|| missing(DataKind::Ship, *model))?;
+                Ok(PlayerShip {
+                    name: name.clone().unwrap_or_else(|| model.name.to_string()),
+                    outfits: model.outfits.clone(),
+                    model: model.clone(),
+                    system: Some(start.system),
+                    planet: Some(start.planet),
+                    parked: false,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let player = &mut self.player;
+        player.date = start.date;
+        player.system = start.system;
+        player.planet = Some(start.planet);
+        player.account = start.account;
+        player.debts = vec![];
+        player.fleet = fleet;
+        player.visited = vec![start.system];
+        player.conditions = vec![(start.set.clone(), 1)];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{start_from_es_data, DataKind, Date, GameDataError, Symbol};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    const PIRATE_START: &str = r#"
+start "Pirate"
+	date 1 1 3014
+	system Arcturus
+	planet Hideout
+	account
+		credits 1000
+		score 0
+	set "license: Pirate"
+	ship "Shuttle" "Bad Sheep"
+	ship "Shuttle"
+
+start "Broken"
+	date 1 1 3014
+	system Arcturus
+	planet Hideout
+	account
+		credits 1000
+		score 0
+	set "license: Pirate"
+	ship "Unknown Model"
+
+system Arcturus
+	pos 1000 0
+	government Pirate
+	habitable 1
+	object Hideout
+		period 10
+
+planet Hideout
+	description `A hideout`
+"#;
+
+    #[test]
+    fn can_begin_from_start() {
+        let game = start_from_es_data(ES_DATA).unwrap();
+        let player = &game.player;
+
+        assert_eq!(game.starts.len(), 1);
+        assert_eq!(
+            player.date,
+            Date {
+                day: 16,
+                month: 11,
+                year: 3013
+            }
+        );
+        assert_eq!(player.system, "Rutilicus");
+        assert_eq!(player.planet, Some(Symbol::intern("New Boston")));
+        assert_eq!(player.account.credits, 480000);
+        assert_eq!(player.account.mortgage.map(|m| m.term), Some(365));
+        assert_eq!(player.visited, vec!["Rutilicus"]);
+        assert_eq!(
+            player.conditions,
+            vec![(String::from("license: Pilot's"), 1)]
+        );
+        assert_eq!(player.fleet.len(), 1);
+        assert_eq!(player.fleet[0].name, "Shuttle");
+        assert_eq!(player.fleet[0].model.fuel_capacity, 400);
+    }
+
+    #[test]
+    fn can_choose_start_by_name() {
+        let mut game = start_from_es_data(&format!("{}{}", ES_DATA, PIRATE_START)).unwrap();
+        assert_eq!(game.starts.len(), 3);
+        // the first start is used by default
+        assert_eq!(game.player.system, "Rutilicus");
+
+        game.begin(Symbol::intern("Pirate")).unwrap();
+        let player = &game.player;
+        assert_eq!(player.system, "Arcturus");
+        assert_eq!(player.planet, Some(Symbol::intern("Hideout")));
+        assert_eq!(player.account.credits, 1000);
+        assert_eq!(player.account.mortgage, None);
+        assert_eq!(
+            player
+                .fleet
+                .iter()
+                .map(|ship| ship.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Bad Sheep", "Shuttle"]
+        );
+
+        game.begin(Symbol::default()).unwrap();
+        assert_eq!(game.player.system, "Rutilicus");
+    }
+
+    #[test]
+    fn will_fail_on_unknown_start_or_references() {
+        let mut game = start_from_es_data(&format!("{}{}", ES_DATA, PIRATE_START)).unwrap();
+
+        let merchant = Symbol::intern("Merchant");
+        assert!(matches!(
+            game.begin(merchant),
+            Err(GameDataError::NoStart(Some(name))) if name == merchant
+        ));
+        assert!(matches!(
+            game.begin(Symbol::intern("Broken")),
+            Err(GameDataError::MissingReference {
+                kind: DataKind::Ship,
+                name
+            }) if name == "Unknown Model"
+        ));
+        // the player is left as is
+        assert_eq!(game.player.system, "Rutilicus");
+        assert_eq!(game.player.fleet.len(), 1);
+    }
+}
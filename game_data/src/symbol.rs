@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::ptr;
+use std::sync::{OnceLock, RwLock};
+
+/// An interned name, like the name of a system or of an outfit
+///
+/// Each distinct name is stored once for the whole program, and symbols of the same name point
+/// to it, so copying, comparing and hashing them doesn't touch the string, and reading it doesn't
+/// need the interner.
+#[derive(Clone, Copy)]
+pub struct Symbol(&'static str);
+
+/// Names interned so far, each leaked once to live as long as the program
+fn interner() -> &'static RwLock<HashSet<&'static str>> {
+    static INTERNER: OnceLock<RwLock<HashSet<&'static str>>> = OnceLock::new();
+    INTERNER.get_or_init(Default::default)
+}
+
+impl Symbol {
+    /// Symbol of a name, interning it if it's new
+    pub fn intern(name: &str) -> Symbol {
+        if let Some(symbol) = Symbol::get(name) {
+            return symbol;
+        }
+        let mut interner = interner().write().unwrap();
+        // another thread may have interned it since the read
+        if let Some(name) = interner.get(name) {
+            return Symbol(name);
+        }
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        interner.insert(name);
+        Symbol(name)
+    }
+
+    /// Symbol of a name if it was already interned, without interning it
+    pub fn get(name: &str) -> Option<Symbol> {
+        interner().read().unwrap().get(name).copied().map(Symbol)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        // interned names are unique, whatever their length
+        ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(self.0, state)
+    }
+}
+
+impl Default for Symbol {
+    fn default() -> Symbol {
+        Symbol::intern("")
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Symbol {
+        Symbol::intern(name)
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Symbol;
+
+    #[test]
+    fn same_names_are_the_same_symbol() {
+        let sol = Symbol::intern("Sol");
+        assert_eq!(sol, Symbol::intern(&String::from("Sol")));
+        assert_ne!(sol, Symbol::intern("Alpha Centauri"));
+        assert_eq!(sol.as_str(), "Sol");
+        assert_eq!(sol, "Sol");
+        assert_eq!(format!("{} {:?}", sol, sol), "Sol \"Sol\"");
+
+        assert_eq!(Symbol::get("Sol"), Some(sol));
+        assert_eq!(Symbol::get("never interned system"), None);
+
+        // empty names are interned too, and the same
+        assert_eq!(Symbol::intern(""), Symbol::default());
+        assert_ne!(Symbol::intern(""), Symbol::intern("Sol"));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Game, Object, Planet, Symbol, System};
+
+impl Object {
+    /// This object and its moons, depth first
+    pub fn descendants(&self) -> Vec<&Object> {
+        let mut objects = vec![self];
+        for child in &self.children {
+            objects.extend(child.descendants());
+        }
+        objects
+    }
+}
+
+impl System {
+    /// All the objects of the system, moons included, parents before their children
+    pub fn all_objects(&self) -> Vec<&Object> {
+        self.objects
+            .iter()
+            .flat_map(|object| object.descendants())
+            .collect()
+    }
+
+    /// Object of the system named after a planet
+    pub fn object(&self, planet: Symbol) -> Option<&Object> {
+        self.all_objects()
+            .into_iter()
+            .find(|object| object.name == Some(planet))
+    }
+}
+
+impl Game {
+    /// Planet definition of a named object
+    pub fn planet_of(&self, object: &Object) -> Option<&Planet> {
+        object.name.and_then(|name| self.planets.get(name))
+    }
+
+    /// System and object where a planet is
+    pub fn location_of(&self, planet: Symbol) -> Option<(&System, &Object)> {
+        self.systems
+            .iter()
+            .find_map(|system| system.object(planet).map(|object| (system, object)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{start_from_es_data, Symbol};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    #[test]
+    fn can_find_planets_in_systems() {
+        let game = start_from_es_data(ES_DATA).unwrap();
+        let rutilicus = &game.systems[0];
+        assert_eq!(rutilicus.objects.len(), 6);
+        assert_eq!(rutilicus.all_objects().len(), 9);
+        assert_eq!(rutilicus.objects[5].children.len(), 2);
+        assert_eq!(
+            rutilicus.objects[5].children[1]
+                .sprite
+                .as_ref()
+                .map(|sprite| sprite.name.as_str()),
+            Some("planet/rock0")
+        );
+
+        let new_boston = Symbol::intern("New Boston");
+        let (system, object) = game.location_of(new_boston).unwrap();
+        assert_eq!(system.name, "Rutilicus");
+        assert_eq!(object.distance, 513.86);
+        assert_eq!(
+            game.planet_of(object).map(|planet| &planet.landscape),
+            Some(&Some(String::from("land/water2")))
+        );
+        assert_eq!(game.planet_of(&rutilicus.objects[0]), None);
+        assert_eq!(game.location_of(Symbol::intern("Earth")), None);
+    }
+
+    #[test]
+    fn can_keep_moons_names_and_offsets() {
+        let data = ES_DATA.replace(
+            "        object\n            sprite planet/rock0\n",
+            "        object Outpost\n            sprite planet/rock0\n            offset 45\n",
+        );
+        let game = start_from_es_data(&data).unwrap();
+
+        let (_, outpost) = game.location_of(Symbol::intern("Outpost")).unwrap();
+        assert_eq!(outpost.offset, 45.);
+        assert_eq!(outpost.distance, 356.);
+        // named, but without planet definition
+        assert_eq!(game.planet_of(outpost), None);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{Drive, GalaxyMap, Game, PlayerShip, Symbol, System};
+
+// Outfit attributes aren't read from the data yet, so drives and ramscoops are recognized by the
+// names of the Endless Sky outfits, and use the fuel of the Endless Sky ones.
+
+/// Fuel used by a jump along a hyperspace link, with the Endless Sky hyperdrive
+pub const HYPERDRIVE_FUEL: f64 = 100.;
+/// Fuel used by a jump along a hyperspace link, with the Endless Sky scram drive
+pub const SCRAM_DRIVE_FUEL: f64 = 150.;
+/// Fuel used by a jump with the Endless Sky jump drive
+pub const JUMP_DRIVE_FUEL: f64 = 200.;
+/// Fuel collected by one ramscoop while crossing a system with a normal solar wind
+pub const RAMSCOOP_FUEL: f64 = 20.;
+/// Part of `RAMSCOOP_FUEL` collected by ships without ramscoop, where ramscoops are universal
+pub const UNIVERSAL_RAMSCOOP: f64 = 0.05;
+
+/// Names of the outfits used as hyperdrives, with the fuel they use
+const HYPERDRIVES: &[(&str, f64)] = &[
+    ("Hyperdrive", HYPERDRIVE_FUEL),
+    ("Scram Drive", SCRAM_DRIVE_FUEL),
+];
+/// Names of the outfits used as jump drives, with the fuel they use
+const JUMP_DRIVES: &[(&str, f64)] = &[("Jump Drive", JUMP_DRIVE_FUEL)];
+/// Names of the outfits used as ramscoops
+const RAMSCOOPS: &[&str] = &["Ramscoop"];
+
+/// A system on a route
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    pub system: Symbol,
+    /// fuel left when arriving in the system, or at the start of the route
+    pub fuel: f64,
+    /// whether the ship must refuel at a spaceport of this system to go on
+    pub refuel: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePlan {
+    /// systems of the route, up to where the ship is stranded if the trip is impossible
+    pub hops: Vec<Hop>,
+    pub possible: bool,
+}
+
+impl Game {
+    /// Plan a trip with the fewest jumps, starting with full fuel
+    ///
+    /// The ship jumps with its hyperdrive along links, and with its jump drive otherwise, going
+    /// along links when it takes as many jumps. With several drives of a kind, the one using the
+    /// least fuel is used, and going through a wormhole uses no fuel. It collects fuel with its
+    /// ramscoops in each system it arrives in, and refuels only when it must, at the last system
+    /// with a spaceport before it would run out of fuel.
+    ///
+    /// Drives and ramscoops are only recognized by the names of the Endless Sky outfits, like
+    /// `Hyperdrive` or `Jump Drive`, as outfit attributes aren't read yet: other outfits with
+    /// the same attributes are ignored.
+    pub fn plan_route(&self, from: Symbol, to: Symbol, ship: &PlayerShip) -> RoutePlan {
+        let count = |names: &[&str]| {
+            ship.outfits
+                .iter()
+                .filter(|(outfit, _)| names.contains(&outfit.as_str()))
+                .map(|(_, count)| *count)
+                .sum::<u32>()
+        };
+        // fuel used by the most efficient of the drives the ship has
+        let fuel = |drives: &[(&str, f64)]| {
+            drives
+                .iter()
+                .filter(|(name, _)| count(&[name]) > 0)
+                .map(|(_, fuel)| *fuel)
+                .reduce(f64::min)
+        };
+        let hyperdrive = fuel(HYPERDRIVES);
+        let jump_drive = fuel(JUMP_DRIVES);
+        let ramscoops = count(RAMSCOOPS);
+        let capacity = f64::from(ship.model.fuel_capacity);
+
+        let start = Hop {
+            system: from,
+            fuel: capacity,
+            refuel: false,
+        };
+        let impossible = RoutePlan {
+            hops: vec![start.clone()],
+            possible: false,
+        };
+        let drive = match (hyperdrive, jump_drive) {
+            (_, Some(_)) => Drive::JumpDrive,
+            (Some(_), None) => Drive::Hyperdrive,
+            (None, None) => return impossible,
+        };
+        let route = match GalaxyMap::new(&self.systems, &self.wormholes, drive).route(from, to) {
+            Some(route) => route,
+            None => return impossible,
+        };
+
+        let mut hops = vec![start];
+        while hops.len() < route.len() {
+            let here = hops.len() - 1;
+            let system = self
+                .systems
+                .get(route[here])
+                .expect("systems of a route are known");
+            let next = self
+                .systems
+                .get(route[here + 1])
+                .expect("systems of a route are known");
+            let linked = system.links.contains(&next.name) || next.links.contains(&system.name);
+            let cost = if self.wormholes.contains(&(system.name, next.name)) {
+                0.
+            } else {
+                match (hyperdrive, jump_drive) {
+                    (Some(fuel), _) if linked => fuel,
+                    (_, Some(fuel)) | (Some(fuel), None) => fuel,
+                    (None, None) => unreachable!("ships without drive don't travel"),
+                }
+            };
+
+            let leaving = |hop: &Hop| if hop.refuel { capacity } else { hop.fuel };
+            if leaving(&hops[here]) < cost {
+                let last_refuel = hops.iter().rposition(|hop| hop.refuel).unwrap_or(0);
+                match (last_refuel..=here).rev().find(|at| {
+                    !hops[*at].refuel
+                        && hops[*at].fuel < capacity
+                        && self.can_refuel(hops[*at].system)
+                }) {
+                    Some(at) => {
+                        hops.truncate(at + 1);
+                        hops[at].refuel = true;
+                        continue;
+                    }
+                    None => {
+                        return RoutePlan {
+                            hops,
+                            possible: false,
+                        }
+                    }
+                }
+            }
+
+            let fuel = (leaving(&hops[here]) - cost + ramscoop_fuel(next, ramscoops)).min(capacity);
+            hops.push(Hop {
+                system: next.name,
+                fuel,
+                refuel: false,
+            });
+        }
+
+        RoutePlan {
+            hops,
+            possible: true,
+        }
+    }
+
+    /// Whether a system has a planet with a spaceport
+    pub fn can_refuel(&self, system: Symbol) -> bool {
+        self.systems.get(system).is_some_and(|system| {
+            system
+                .all_objects()
+                .into_iter()
+                .filter_map(|object| object.name)
+                .filter_map(|planet| self.planets.get(planet))
+                .any(|planet| !planet.spaceport.is_empty())
+        })
+    }
+}
+
+fn ramscoop_fuel(system: &System, ramscoops: u32) -> f64 {
+    let universal = system
+        .ramscoop
+        .and_then(|ramscoop| ramscoop.universal)
+        .unwrap_or(true);
+    let multiplier = system
+        .ramscoop
+        .and_then(|ramscoop| ramscoop.multiplier)
+        .map_or(1., f64::from);
+    let addend = system
+        .ramscoop
+        .and_then(|ramscoop| ramscoop.addend)
+        .map_or(0., f64::from);
+
+    let scoop = f64::from(ramscoops).sqrt() + if universal { UNIVERSAL_RAMSCOOP } else { 0. };
+    (RAMSCOOP_FUEL * scoop * multiplier + addend).max(0.)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{start_from_es_data, Hop, PlayerShip, Symbol};
+
+    const ES_DATA: &str = include_str!("../../data/simple_game.txt");
+
+    // Rutilicus - Arcturus - Vega - Altair - Deneb - Sirius, with a spaceport in Altair
+    const GALAXY: &str = r#"
+system Arcturus
+	pos 1000 0
+	government Republic
+	habitable 1
+	link Rutilicus
+	link Vega
+system Vega
+	pos 2000 0
+	government Republic
+	habitable 1
+	link Altair
+system Altair
+	pos 3000 0
+	government Republic
+	habitable 1
+	link Deneb
+	object Port
+		period 10
+system Deneb
+	pos 4000 0
+	government Republic
+	habitable 1
+	link Sirius
+system Sirius
+	pos 5000 0
+	government Republic
+	habitable 1
+	ramscoop
+		universal 0
+planet Port
+	description `A port`
+	spaceport `A spaceport`
+"#;
+
+    fn ship(outfits: &[(&str, u32)]) -> PlayerShip {
+        let game = start_from_es_data(ES_DATA).unwrap();
+        PlayerShip {
+            name: String::from("Bad Sheep"),
+            model: game.ships.get_by_name("Shuttle").unwrap().clone(),
+            outfits: outfits
+                .iter()
+                .map(|(outfit, count)| (Symbol::intern(outfit), *count))
+                .collect(),
+            system: None,
+            planet: None,
+            parked: false,
+        }
+    }
+
+    fn hop(system: &str, fuel: f64, refuel: bool) -> Hop {
+        Hop {
+            system: Symbol::intern(system),
+            fuel,
+            refuel,
+        }
+    }
+
+    #[test]
+    fn can_plan_route_with_refuelling() {
+        let game = start_from_es_data(&format!("{}{}", ES_DATA, GALAXY)).unwrap();
+        assert!(game.can_refuel("Rutilicus".into()));
+        assert!(game.can_refuel("Altair".into()));
+        assert!(!game.can_refuel("Vega".into()));
+
+        let plan = game.plan_route(
+            "Rutilicus".into(),
+            "Sirius".into(),
+            &ship(&[("Hyperdrive", 1)]),
+        );
+        assert!(plan.possible);
+        assert_eq!(
+            plan.hops,
+            vec![
+                hop("Rutilicus", 400., false),
+                hop("Arcturus", 301., false),
+                hop("Vega", 202., false),
+                hop("Altair", 103., true),
+                hop("Deneb", 301., false),
+                hop("Sirius", 201., false),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_prefer_links_with_both_drives() {
+        // Antares is two jumps away from Rutilicus along links through Wolf, and with a jump
+        // drive through Procyon, which is found first
+        const SHORTCUTS: &str = r#"
+system Procyon
+	pos -500 273
+	government Republic
+	habitable 1
+	"jump range" 3000
+system Wolf
+	pos -500 1000
+	government Republic
+	habitable 1
+	link Rutilicus
+	link Antares
+system Antares
+	pos 2000 1500
+	government Republic
+	habitable 1
+"#;
+        let game = start_from_es_data(&format!("{}{}{}", ES_DATA, GALAXY, SHORTCUTS)).unwrap();
+        let drives = ship(&[("Hyperdrive", 1), ("Jump Drive", 1)]);
+
+        let plan = game.plan_route("Rutilicus".into(), "Antares".into(), &drives);
+        assert!(plan.possible);
+        assert_eq!(
+            plan.hops,
+            vec![
+                hop("Rutilicus", 400., false),
+                hop("Wolf", 301., false),
+                hop("Antares", 202., false),
+            ]
+        );
+        // the jump drive is used where there is no link
+        let plan = game.plan_route("Rutilicus".into(), "Procyon".into(), &drives);
+        assert_eq!(plan.hops.last(), Some(&hop("Procyon", 201., false)));
+    }
+
+    #[test]
+    fn can_use_the_fuel_of_each_drive() {
+        let game = start_from_es_data(&format!("{}{}", ES_DATA, GALAXY)).unwrap();
+
+        let plan = game.plan_route(
+            "Rutilicus".into(),
+            "Vega".into(),
+            &ship(&[("Scram Drive", 1)]),
+        );
+        assert!(plan.possible);
+        assert_eq!(
+            plan.hops,
+            vec![
+                hop("Rutilicus", 400., false),
+                hop("Arcturus", 251., false),
+                hop("Vega", 102., false),
+            ]
+        );
+
+        // the hyperdrive uses less fuel than the scram drive
+        let plan = game.plan_route(
+            "Rutilicus".into(),
+            "Vega".into(),
+            &ship(&[("Scram Drive", 1), ("Hyperdrive", 1)]),
+        );
+        assert_eq!(plan.hops.last(), Some(&hop("Vega", 202., false)));
+    }
+
+    #[test]
+    fn can_go_through_wormholes_without_fuel() {
+        const WORMHOLE: &str = r#"
+wormhole Gate
+	link Rutilicus Sirius
+"#;
+        let game = start_from_es_data(&format!("{}{}{}", ES_DATA, GALAXY, WORMHOLE)).unwrap();
+
+        let plan = game.plan_route(
+            "Rutilicus".into(),
+            "Sirius".into(),
+            &ship(&[("Hyperdrive", 1)]),
+        );
+        assert!(plan.possible);
+        assert_eq!(
+            plan.hops,
+            vec![hop("Rutilicus", 400., false), hop("Sirius", 400., false)]
+        );
+    }
+
+    #[test]
+    fn can_find_impossible_trips() {
+        let game = start_from_es_data(&format!("{}{}", ES_DATA, GALAXY)).unwrap();
+
+        let plan = game.plan_route(
+            "Rutilicus".into(),
+            "Sirius".into(),
+            &ship(&[("Jump Drive", 1), ("Ramscoop", 4)]),
+        );
+        assert!(!plan.possible);
+        assert_eq!(
+            plan.hops,
+            vec![
+                hop("Rutilicus", 400., false),
+                hop("Arcturus", 241., false),
+                hop("Vega", 82., false),
+            ]
+        );
+
+        let plan = game.plan_route("Rutilicus".into(), "Arcturus".into(), &ship(&[]));
+        assert!(!plan.possible);
+        assert_eq!(plan.hops, vec![hop("Rutilicus", 400., false)]);
+    }
+}